@@ -0,0 +1,43 @@
+//! Runtime configuration helpers. Everything is configured through `WAYBAP_*` env vars
+//! (set them in the systemd unit / home-manager module), there is no config file.
+use std::str::FromStr;
+
+/// Parse `name` into `T`, or `default` if unset. Invalid values are logged and ignored.
+pub fn env_parse<T: FromStr>(name: &str, default: T) -> T {
+    match std::env::var(name) {
+        Ok(s) => match s.trim().parse() {
+            Ok(v) => v,
+            Err(_) => {
+                eprintln!("{name} has an invalid value '{s}', using default");
+                default
+            }
+        },
+        Err(_) => default,
+    }
+}
+
+/// Boolean switch: accepts 1/true/on/yes and 0/false/off/no (case-insensitive).
+pub fn env_flag(name: &str, default: bool) -> bool {
+    match std::env::var(name) {
+        Ok(s) => match s.trim().to_lowercase().as_str() {
+            "1" | "true" | "on" | "yes" => true,
+            "0" | "false" | "off" | "no" => false,
+            _ => {
+                eprintln!("{name} has an invalid value '{s}' (expected on/off), using default");
+                default
+            }
+        },
+        Err(_) => default,
+    }
+}
+
+/// Comma-separated list, trimmed with empty items dropped. `None` if unset.
+pub fn env_list(name: &str) -> Option<Vec<String>> {
+    let s = std::env::var(name).ok()?;
+    Some(
+        s.split(',')
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .collect(),
+    )
+}
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::process::Command;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{env_flag, env_parse};
+use crate::crypto::currency::Currency;
+use crate::crypto::model::Coin;
+use crate::crypto::options::CHANGE_WINDOWS;
+use crate::scheduler::get_cache_fp;
+
+pub enum Condition {
+    Below(f64),
+    Above(f64),
+    Moves { percent: f64, window: String }, // |change over window| >= percent, either direction
+}
+
+/// A WAYBAP_CRYPTO_ALERTS rule: `bitcoin<60k`, `bitcoin>80000`, `ethereum~5%/1h`.
+pub struct AlertRule {
+    pub id: String,
+    pub condition: Condition,
+}
+
+/// "60000", "60k", "1.5M".
+fn parse_number(s: &str) -> Option<f64> {
+    let s = s.trim();
+    let (number, scale) = match s.chars().last()? {
+        'k' | 'K' => (&s[..s.len() - 1], 1e3),
+        'm' | 'M' => (&s[..s.len() - 1], 1e6),
+        _ => (s, 1.0),
+    };
+    Some(number.trim().parse::<f64>().ok()? * scale)
+}
+
+impl AlertRule {
+    pub fn parse(entry: &str) -> Option<AlertRule> {
+        let at = entry.find(['<', '>', '~'])?;
+        let id = entry[..at].trim().to_lowercase();
+        if id.is_empty() {
+            return None;
+        }
+        let rest = &entry[at + 1..];
+        let condition = match &entry[at..at + 1] {
+            "<" => Condition::Below(parse_number(rest)?),
+            ">" => Condition::Above(parse_number(rest)?),
+            _ => {
+                let (percent, window) = rest.split_once('/').unwrap_or((rest, "24h"));
+                let window = window.trim().to_lowercase();
+                if !CHANGE_WINDOWS.contains(&window.as_str()) {
+                    return None;
+                }
+                Condition::Moves {
+                    percent: percent.trim().trim_end_matches('%').parse().ok()?,
+                    window,
+                }
+            }
+        };
+        Some(AlertRule { id, condition })
+    }
+
+    /// Is the rule met by `coin`? `None` when the data to tell is missing.
+    /// `active` rules need to clear the threshold by `hysteresis` percent to turn off.
+    fn is_met(&self, coin: &Coin, active: bool, hysteresis: f64) -> Option<bool> {
+        let margin = if active { hysteresis / 100.0 } else { 0.0 };
+        Some(match &self.condition {
+            Condition::Below(threshold) => coin.price < threshold * (1.0 + margin),
+            Condition::Above(threshold) => coin.price > threshold * (1.0 - margin),
+            // @NOTE: For moves the hysteresis is in percentage points: a 5% rule with 1% hysteresis
+            //   stays on until the move is back under 4%.
+            Condition::Moves { percent, window } => {
+                coin.change_over(window)?.abs() >= percent - if active { hysteresis } else { 0.0 }
+            }
+        })
+    }
+
+    fn message(&self, coin: &Coin, currency: &Currency) -> String {
+        match &self.condition {
+            Condition::Below(threshold) => format!(
+                "{} below {}: {}",
+                coin.name,
+                currency.format_price(*threshold),
+                currency.format_price(coin.price)
+            ),
+            Condition::Above(threshold) => format!(
+                "{} above {}: {}",
+                coin.name,
+                currency.format_price(*threshold),
+                currency.format_price(coin.price)
+            ),
+            Condition::Moves { window, .. } => format!(
+                "{} moved {:+.1}% in {window}: {}",
+                coin.name,
+                coin.change_over(window).unwrap_or(0.0),
+                currency.format_price(coin.price)
+            ),
+        }
+    }
+}
+
+// Normalized rule text, used as the state key.
+impl fmt::Display for AlertRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.condition {
+            Condition::Below(threshold) => write!(f, "{}<{threshold}", self.id),
+            Condition::Above(threshold) => write!(f, "{}>{threshold}", self.id),
+            Condition::Moves { percent, window } => write!(f, "{}~{percent}%/{window}", self.id),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ActiveAlert {
+    pub message: String,
+    pub acknowledged: bool,
+}
+
+// @NOTE: Alert state lives in its own cache file rather than in crypto.json: it's written by the
+//   scheduler after each fetch *and* by the server on acknowledge, and must survive restarts so the
+//   hysteresis and acknowledgements aren't reset.
+static STATE_LOCK: Mutex<()> = Mutex::new(());
+
+fn state_fp() -> String {
+    get_cache_fp("crypto-alerts")
+}
+
+/// Active alerts by rule, as of the last fetch.
+pub fn load_state() -> HashMap<String, ActiveAlert> {
+    fs::read_to_string(state_fp())
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save_state(state: &HashMap<String, ActiveAlert>) {
+    let fp = state_fp();
+    let result = serde_json::to_string(state)
+        .map_err(|err| err.to_string())
+        .and_then(|json| fs::write(&fp, json).map_err(|err| err.to_string()));
+    if let Err(err) = result {
+        eprintln!("ERROR: failed to write crypto alert state '{fp}': {err}");
+    }
+}
+
+fn notify(message: &str) {
+    let result = Command::new("notify-send")
+        .args(["--app-name=waybap", "--urgency=critical", "Crypto alert", message])
+        .status();
+    if let Err(err) = result {
+        eprintln!("ERROR: failed to run notify-send: {err}");
+    }
+}
+
+/// Scheduler-side: update alert state for freshly fetched coins and notify on newly triggered rules.
+/// A rule whose coin is missing from the response keeps its previous state.
+pub fn evaluate(rules: &[AlertRule], coins: &[Coin], currency: &Currency) {
+    let hysteresis = env_parse("WAYBAP_CRYPTO_ALERT_HYSTERESIS", 1.0_f64).max(0.0);
+    let notify_enabled = env_flag("WAYBAP_CRYPTO_ALERT_NOTIFY", false);
+
+    let _lock = STATE_LOCK.lock().unwrap();
+    let mut previous = load_state();
+    let mut state = HashMap::new();
+    for rule in rules {
+        let key = rule.to_string();
+        let was_active = previous.get(&key);
+        let coin = coins.iter().find(|c| c.id == rule.id);
+        match coin.and_then(|coin| Some((coin, rule.is_met(coin, was_active.is_some(), hysteresis)?))) {
+            Some((coin, true)) => {
+                let message = rule.message(coin, currency);
+                if was_active.is_none() && notify_enabled {
+                    notify(&message);
+                }
+                let acknowledged = was_active.is_some_and(|a| a.acknowledged);
+                state.insert(key, ActiveAlert { message, acknowledged });
+            }
+            Some((_, false)) => {}
+            None => {
+                if let Some(alert) = previous.remove(&key) {
+                    state.insert(key, alert);
+                }
+            }
+        }
+    }
+    save_state(&state);
+}
+
+/// Server-side: mark all active alerts as seen. Returns how many were newly acknowledged.
+pub fn acknowledge() -> usize {
+    let _lock = STATE_LOCK.lock().unwrap();
+    let mut state = load_state();
+    let mut count = 0;
+    for alert in state.values_mut().filter(|a| !a.acknowledged) {
+        alert.acknowledged = true;
+        count += 1;
+    }
+    save_state(&state);
+    count
+}
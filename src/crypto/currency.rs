@@ -0,0 +1,91 @@
+/// Display rules for a CoinGecko `vs_currency`.
+pub struct Currency {
+    pub code: String, // lowercase, as CoinGecko expects it
+    symbol: &'static str,
+    suffix: bool, // "12.50 zł" rather than "$12.50"
+    decimals: usize,
+}
+
+// (code, symbol, suffix, decimals)
+const CURRENCIES: &[(&str, &str, bool, usize)] = &[
+    ("usd", "$", false, 2),
+    ("eur", "€", false, 2),
+    ("gbp", "£", false, 2),
+    ("chf", "CHF ", false, 2),
+    ("jpy", "¥", false, 0),
+    ("cny", "¥", false, 2),
+    ("krw", "₩", false, 0),
+    ("inr", "₹", false, 2),
+    ("cad", "C$", false, 2),
+    ("aud", "A$", false, 2),
+    ("uah", "₴", true, 2),
+    ("pln", "zł", true, 2),
+    ("czk", "Kč", true, 2),
+    ("sek", "kr", true, 2),
+    ("nok", "kr", true, 2),
+    ("try", "₺", false, 2),
+    ("btc", "₿", false, 8),
+    ("eth", "Ξ", false, 6),
+    ("sats", "sats", true, 0),
+];
+
+// Significant digits shown for prices (matches the original "$67234.5" / "$1.00020" layout).
+const PRICE_DIGITS: i32 = 6;
+
+impl Currency {
+    pub fn from_code(code: &str) -> Currency {
+        let code = code.to_lowercase();
+        match CURRENCIES.iter().find(|(c, _, _, _)| *c == code) {
+            Some(&(_, symbol, suffix, decimals)) => Currency {
+                code,
+                symbol,
+                suffix,
+                decimals,
+            },
+            // Anything else CoinGecko supports still works, just labelled with its code
+            None => Currency {
+                code,
+                symbol: "",
+                suffix: true,
+                decimals: 2,
+            },
+        }
+    }
+
+    fn wrap(&self, number: &str) -> String {
+        match (self.symbol, self.suffix) {
+            ("", _) => format!("{number} {}", self.code.to_uppercase()),
+            (symbol, true) => format!("{number} {symbol}"),
+            (symbol, false) => format!("{symbol}{number}"),
+        }
+    }
+
+    /// Price with ~6 significant digits ("$67234.5", "$1.00020", "0.0000123 ₴"), or the full
+    /// precision of crypto quote currencies ("₿0.05141234").
+    pub fn format_price(&self, price: f64) -> String {
+        let abs = price.abs();
+        let decimals = if self.decimals > 2 {
+            self.decimals
+        } else if abs >= 1.0 {
+            (PRICE_DIGITS - (abs.log10().floor() as i32 + 1)).max(0) as usize
+        } else if abs > 0.0 {
+            // At least 5 decimals, more for sub-cent prices so 3 significant digits survive
+            ((-abs.log10()).floor() as usize + 3).max(5)
+        } else {
+            self.decimals
+        };
+        self.wrap(&format!("{price:.decimals$}"))
+    }
+
+    /// Amount in the currency's usual precision: "$1234.56", "1234.56 zł", "₿0.01234567".
+    pub fn format_amount(&self, amount: f64) -> String {
+        let decimals = self.decimals;
+        self.wrap(&format!("{amount:.decimals$}"))
+    }
+
+    /// Signed amount for changes: "+$12.34", "−12.34 zł".
+    pub fn format_change(&self, amount: f64) -> String {
+        let sign = if amount < 0.0 { "−" } else { "+" };
+        format!("{sign}{}", self.format_amount(amount.abs()))
+    }
+}
@@ -1,5 +1,14 @@
+mod alerts;
+mod currency;
+mod model;
+mod options;
 mod parsing;
+mod portfolio;
+mod providers;
 mod query;
+mod search;
 
+pub use alerts::acknowledge;
 pub use parsing::parse_data;
 pub use query::query;
+pub use search::search;
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use serde_aux::prelude::*;
+
+/// One coin's market data. Field names follow CoinGecko's `/coins/markets` response, which is also
+/// the cache format; other providers fill what they have and leave the rest `None`.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Coin {
+    pub id: String,
+    pub name: String,
+    pub symbol: String,
+    #[serde(rename = "current_price")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub price: f64,
+    #[serde(rename = "price_change_percentage_24h")]
+    pub change: Option<f64>,
+    // Only present for the windows requested with `price_change_percentage=`
+    #[serde(default, rename = "price_change_percentage_1h_in_currency")]
+    pub change_1h: Option<f64>,
+    #[serde(default, rename = "price_change_percentage_7d_in_currency")]
+    pub change_7d: Option<f64>,
+    #[serde(default, rename = "price_change_percentage_30d_in_currency")]
+    pub change_30d: Option<f64>,
+    #[serde(default, rename = "sparkline_in_7d")]
+    pub sparkline: Option<Sparkline>, // with `sparkline=true`
+}
+
+/// Hourly prices over the last 7 days, oldest first.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Sparkline {
+    pub price: Vec<f64>,
+}
+
+impl Coin {
+    /// Percent change over a window from CHANGE_WINDOWS ("1h", "24h", "7d", "30d").
+    pub fn change_over(&self, window: &str) -> Option<f64> {
+        match window {
+            "1h" => self.change_1h,
+            "24h" => self.change,
+            "7d" => self.change_7d,
+            "30d" => self.change_30d,
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use crate::config::{env_flag, env_list, env_parse};
+use crate::crypto::alerts::{AlertRule, Condition};
+use crate::crypto::currency::Currency;
+use crate::crypto::model::Coin;
+
+/// How a coin is drawn in the bar.
+pub struct BarCoin {
+    pub id: String,
+    pub glyph: Option<String>, // None = the coin's ticker
+    pub color: String,
+}
+
+// Built-in glyphs/brand colors. Coins without a Nerd Font glyph show their ticker;
+// unknown coins show their ticker in lavender.
+const KNOWN_COINS: &[(&str, Option<&str>, &str)] = &[
+    ("bitcoin", Some("\u{F0813}"), "#F7931A"),  // 󰠓 nf-md-bitcoin
+    ("ethereum", Some("\u{F086A}"), "#8C8CE6"), // 󰡪 nf-md-ethereum (lightened for dark bars)
+    ("litecoin", Some("\u{F0A61}"), "#A6A9AA"), // 󰩡 nf-md-litecoin
+    ("solana", None, "#9945FF"),
+    ("monero", None, "#FF6600"),
+    ("dogecoin", None, "#C2A633"),
+    ("tether", None, "#26A17B"),
+];
+
+// Change windows WAYBAP_CRYPTO_CHANGES can show, as CoinGecko's `price_change_percentage` names them.
+pub const CHANGE_WINDOWS: &[&str] = &["1h", "24h", "7d", "30d"];
+
+/// A position from WAYBAP_CRYPTO_HOLDINGS.
+pub struct Holding {
+    pub id: String,
+    pub amount: f64,
+    pub cost: Option<f64>, // total paid, in the quote currency
+}
+
+impl Holding {
+    /// Parse `id:amount` or `id:amount:cost`.
+    fn parse(entry: &str) -> Option<Holding> {
+        let mut parts = entry.split(':').map(str::trim);
+        let id = parts.next().filter(|id| !id.is_empty())?.to_lowercase();
+        let amount = parts.next()?.parse().ok()?;
+        let cost = match parts.next() {
+            Some(cost) => Some(cost.parse().ok()?),
+            None => None,
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Holding { id, amount, cost })
+    }
+}
+
+/// Crypto module configuration, read from WAYBAP_* env vars.
+pub struct CryptoOptions {
+    pub watchlist: Option<Vec<String>>, // CoinGecko ids in display order; None = top 10 by market cap
+    pub bar: Vec<BarCoin>,
+    pub currency: Currency,                   // quote currency for prices
+    pub secondary_currency: Option<Currency>, // extra tooltip column
+    pub holdings: Vec<Holding>,
+    pub bar_portfolio: bool,      // bar shows total portfolio value instead of coin prices
+    pub changes: Vec<String>,     // change columns, from CHANGE_WINDOWS
+    pub sparkline: Option<usize>, // 7d sparkline width in characters; None = off
+    pub alerts: Vec<AlertRule>,
+}
+
+impl BarCoin {
+    /// Parse a WAYBAP_CRYPTO_BAR entry: `id`, `id:glyph` or `id:glyph:#color`.
+    fn parse(entry: &str) -> BarCoin {
+        let mut parts = entry.splitn(3, ':');
+        let id = parts.next().unwrap_or("").to_lowercase();
+        let known = KNOWN_COINS.iter().find(|(known_id, _, _)| *known_id == id);
+        let glyph = parts
+            .next()
+            .filter(|g| !g.is_empty())
+            .map(str::to_string)
+            .or_else(|| known.and_then(|(_, glyph, _)| glyph.map(str::to_string)));
+        let color = parts
+            .next()
+            .filter(|c| !c.is_empty())
+            .map(str::to_string)
+            .or_else(|| known.map(|(_, _, color)| color.to_string()))
+            .unwrap_or("#babbf1".to_string());
+        BarCoin { id, glyph, color }
+    }
+}
+
+impl CryptoOptions {
+    pub fn from_env() -> CryptoOptions {
+        let watchlist = env_list("WAYBAP_CRYPTO_COINS")
+            .map(|ids| ids.into_iter().map(|id| id.to_lowercase()).collect::<Vec<_>>())
+            .filter(|ids| !ids.is_empty());
+        let bar = env_list("WAYBAP_CRYPTO_BAR")
+            .filter(|entries| !entries.is_empty())
+            .unwrap_or_else(|| vec!["bitcoin".to_string()])
+            .iter()
+            .map(|entry| BarCoin::parse(entry))
+            .collect();
+        let currency = std::env::var("WAYBAP_CRYPTO_CURRENCY")
+            .ok()
+            .filter(|c| !c.trim().is_empty())
+            .unwrap_or("usd".to_string());
+        let secondary_currency = std::env::var("WAYBAP_CRYPTO_SECONDARY_CURRENCY")
+            .ok()
+            .filter(|c| !c.trim().is_empty())
+            .map(|c| Currency::from_code(c.trim()));
+        let holdings = env_list("WAYBAP_CRYPTO_HOLDINGS")
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|entry| {
+                let holding = Holding::parse(entry);
+                if holding.is_none() {
+                    eprintln!("WAYBAP_CRYPTO_HOLDINGS: invalid entry '{entry}' (expected id:amount[:cost])");
+                }
+                holding
+            })
+            .collect::<Vec<_>>();
+        let changes = env_list("WAYBAP_CRYPTO_CHANGES")
+            .unwrap_or_else(|| CHANGE_WINDOWS.iter().map(|w| w.to_string()).collect())
+            .into_iter()
+            .map(|w| w.to_lowercase())
+            .filter(|w| {
+                let known = CHANGE_WINDOWS.contains(&w.as_str());
+                if !known {
+                    eprintln!(
+                        "WAYBAP_CRYPTO_CHANGES: unknown window '{w}' (known: {})",
+                        CHANGE_WINDOWS.join(", ")
+                    );
+                }
+                known
+            })
+            .collect();
+
+        let alerts = env_list("WAYBAP_CRYPTO_ALERTS")
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|entry| {
+                let rule = AlertRule::parse(entry);
+                if rule.is_none() {
+                    eprintln!(
+                        "WAYBAP_CRYPTO_ALERTS: invalid rule '{entry}' (expected id<price, id>price or id~percent%/window)"
+                    );
+                }
+                rule
+            })
+            .collect();
+
+        CryptoOptions {
+            watchlist,
+            bar,
+            currency: Currency::from_code(currency.trim()),
+            secondary_currency,
+            bar_portfolio: env_flag("WAYBAP_CRYPTO_BAR_PORTFOLIO", false) && !holdings.is_empty(),
+            holdings,
+            changes,
+            sparkline: env_flag("WAYBAP_CRYPTO_SPARKLINE", true)
+                .then(|| env_parse("WAYBAP_CRYPTO_SPARKLINE_WIDTH", 24_usize).clamp(4, 168)),
+            alerts,
+        }
+    }
+
+    /// Change windows to request: 24h (the bar and portfolio are colored by it), the tooltip
+    /// columns and any windows alert rules watch.
+    pub fn fetch_windows(&self) -> Vec<&str> {
+        let mut windows = vec!["24h"];
+        let alert_windows = self.alerts.iter().filter_map(|rule| match &rule.condition {
+            Condition::Moves { window, .. } => Some(window),
+            _ => None,
+        });
+        for window in self.changes.iter().chain(alert_windows) {
+            if !windows.contains(&window.as_str()) {
+                windows.push(window);
+            }
+        }
+        windows
+    }
+
+    /// Coins needed whatever the watchlist says: bar coins, holdings and alert coins.
+    fn required_ids(&self) -> impl Iterator<Item = &String> {
+        self.bar
+            .iter()
+            .map(|c| &c.id)
+            .chain(self.holdings.iter().map(|h| &h.id))
+            .chain(self.alerts.iter().map(|a| &a.id))
+    }
+
+    /// Ids to request: the watchlist plus any bar coins, holdings and alert coins not on it.
+    /// `None` in top-10 mode, see `missing_ids`.
+    pub fn fetch_ids(&self) -> Option<Vec<String>> {
+        let mut ids = self.watchlist.clone()?;
+        for id in self.required_ids() {
+            if !ids.contains(id) {
+                ids.push(id.clone());
+            }
+        }
+        Some(ids)
+    }
+
+    /// Required ids that aren't among `coins`: in top-10 mode, the ones outside the top 10, which
+    /// need their own `ids=` request.
+    pub fn missing_ids(&self, coins: &[Coin]) -> Vec<String> {
+        let mut ids: Vec<String> = Vec::new();
+        for id in self.required_ids() {
+            if !ids.contains(id) && !coins.iter().any(|c| c.id == *id) {
+                ids.push(id.clone());
+            }
+        }
+        ids
+    }
+}
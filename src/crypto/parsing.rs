@@ -1,67 +1,267 @@
+use std::collections::HashMap;
+
 use serde::Deserialize;
-use serde_aux::prelude::*;
 use serde_json::{json, value::from_value, Value};
 
-#[derive(Deserialize, Debug)]
-struct Coin {
-    name: String,
-    symbol: String,
-    #[serde(rename = "current_price")]
-    #[serde(deserialize_with = "deserialize_number_from_string")]
-    price: f64,
-    #[serde(rename = "price_change_percentage_24h")]
-    change: Option<f64>,
+use crate::crypto::alerts;
+use crate::crypto::currency::Currency;
+use crate::crypto::model::Coin;
+use crate::crypto::options::{BarCoin, CryptoOptions};
+use crate::crypto::portfolio;
+
+#[derive(Deserialize)]
+struct QueryWrapper {
+    currency: String, // vs_currency the prices are in
+    #[serde(default)]
+    provider: Option<String>, // missing in caches from before providers were pluggable
+    coins: Vec<Coin>,
+    // Bar coins, holdings and alert coins outside the top 10, in top-10 mode
+    #[serde(default)]
+    extra: Vec<Coin>,
+    secondary: Option<SecondaryPrices>,
+}
+
+/// The cache as written by this version, or the bare `/coins/markets` array (always in USD)
+/// written before currencies were added, which is still served until the next fetch.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CachedCrypto {
+    Wrapped(QueryWrapper),
+    Bare(Vec<Coin>),
+}
+
+impl From<CachedCrypto> for QueryWrapper {
+    fn from(cached: CachedCrypto) -> QueryWrapper {
+        match cached {
+            CachedCrypto::Wrapped(wrapper) => wrapper,
+            CachedCrypto::Bare(coins) => QueryWrapper {
+                currency: "usd".to_string(),
+                provider: None,
+                coins,
+                extra: Vec::new(),
+                secondary: None,
+            },
+        }
+    }
+}
+
+/// Secondary-currency quotes from whichever provider could serve that currency.
+#[derive(Deserialize)]
+struct SecondaryPrices {
+    currency: String,
+    prices: HashMap<String, HashMap<String, f64>>, // {coin id: {currency code: price}}
+}
+
+/// Short bar price with a magnitude suffix: "67.2k", "1.3M", "145", "0.1234".
+fn format_compact(price: f64) -> String {
+    let abs = price.abs();
+    if abs >= 1e9 {
+        format!("{:.1}B", price / 1e9)
+    } else if abs >= 1e6 {
+        format!("{:.1}M", price / 1e6)
+    } else if abs >= 1e3 {
+        format!("{:.1}k", price / 1e3)
+    } else if abs >= 100.0 {
+        format!("{price:.0}")
+    } else if abs >= 1.0 {
+        format!("{price:.2}")
+    } else if abs > 0.0 {
+        // Keep 3 significant digits for sub-cent coins
+        let decimals = (-abs.log10()).floor() as usize + 3;
+        format!("{price:.decimals$}")
+    } else {
+        "0".to_string()
+    }
+}
+
+/// 7d price trend, green if the price ended higher than it started and red otherwise.
+fn format_sparkline(prices: &[f64], width: usize) -> String {
+    crate::pango::sparkline(prices, width, |points| match (points.first(), points.last()) {
+        (Some(first), Some(last)) if last < first => "#e78284",
+        _ => "#a6d189",
+    })
+}
+
+/// One coin in the bar: glyph (or ticker) over its compact price, colored by 24h change.
+fn format_bar_coin(bar: &BarCoin, coin: Option<&Coin>) -> String {
+    let glyph = match (&bar.glyph, coin) {
+        (Some(glyph), _) => format!("<span size=\"large\" foreground=\"{}\"> {glyph}</span>", bar.color),
+        (None, Some(coin)) => format!(
+            "<span size=\"small\" foreground=\"{}\"><b>{}</b></span>",
+            bar.color,
+            crate::pango::escape(&coin.symbol.to_uppercase())
+        ),
+        (None, None) => format!(
+            "<span size=\"small\" foreground=\"{}\"><b>{}</b></span>",
+            bar.color,
+            crate::pango::escape(&bar.id)
+        ),
+    };
+    let price = match coin {
+        Some(coin) => {
+            let color = if coin.change.unwrap_or(0.0) < 0.0 {
+                "#e78284"
+            } else {
+                "#a6d189"
+            };
+            format!(
+                "<span foreground=\"{color}\" size=\"x-small\">{}</span>",
+                format_compact(coin.price)
+            )
+        }
+        // Not in the response: not on the watchlist/top 10 yet, or an unknown id
+        None => "<span foreground=\"#949cbb\" size=\"x-small\">N/A</span>".to_string(),
+    };
+    format!("{glyph}\n{price}")
 }
 
 pub fn parse_data(raw_crypto: Value) -> Result<String, Box<dyn std::error::Error>> {
-    let coins = from_value::<Vec<Coin>>(raw_crypto)?;
+    let wrapper = QueryWrapper::from(from_value::<CachedCrypto>(raw_crypto)?);
+    let mut coins = wrapper.coins;
+    // The bar and portfolio see every fetched coin, the price table only `coins`
+    let table_len = coins.len();
+    coins.extend(wrapper.extra);
+    let options = CryptoOptions::from_env();
+    // Format with the currency the cached data is in, which may lag a config change by one fetch
+    let currency = Currency::from_code(&wrapper.currency);
+    let secondary = wrapper.secondary.map(|s| (Currency::from_code(&s.currency), s.prices));
 
     // @NOTE: You can't put 'class' on the span here for some reason, but you
     //        can change a bunch of things directly with this special subset
     //        of html (bruh): https://docs.gtk.org/Pango/pango_markup.html
-    let mut text = "<span size=\"large\" foreground=\"#F7931A\"> 󰠓</span>\n".to_string(); // Using bitcoin orange.
+    let text = if options.bar_portfolio {
+        let totals = portfolio::totals(&options.holdings, &coins);
+        let color = if totals.change_24h < 0.0 { "#e78284" } else { "#a6d189" };
+        format!(
+            "<span size=\"large\" foreground=\"#babbf1\"> \u{F0584}</span>\n<span foreground=\"{color}\" size=\"x-small\">{}</span>", // 󰖄 nf-md-wallet
+            format_compact(totals.value)
+        )
+    } else {
+        options
+            .bar
+            .iter()
+            .map(|bar| format_bar_coin(bar, coins.iter().find(|c| c.id == bar.id)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    // Before the watchlist filter: holdings don't have to be on the watchlist
+    let portfolio_section = if options.holdings.is_empty() {
+        String::new()
+    } else {
+        portfolio::render(&options.holdings, &coins, &currency)
+    };
+    coins.truncate(table_len);
+
+    // CoinGecko returns `ids=` results by market cap; show them in the configured order instead.
+    // Ids it doesn't know are silently dropped from the response, so list them at the bottom.
+    let mut missing = Vec::new();
+    if let Some(ids) = options.watchlist {
+        coins.retain(|c| ids.contains(&c.id)); // cache may predate a watchlist change
+        coins.sort_by_key(|c| ids.iter().position(|id| *id == c.id).unwrap_or(usize::MAX));
+        missing = ids
+            .into_iter()
+            .filter(|id| !coins.iter().any(|c| c.id == *id))
+            .collect();
+    }
+
     let mut tooltip = "<span size=\"xx-large\">Crypto</span>\n".to_string();
-    let max_name_len = coins
+    // Triggered alerts first, in rule order; acknowledged ones stay listed (muted) until they clear
+    let alert_state = alerts::load_state();
+    let active: Vec<_> = options
+        .alerts
         .iter()
-        .map(|c| crate::pango::escape(&c.name).len())
+        .filter_map(|rule| alert_state.get(&rule.to_string()))
+        .collect();
+    for alert in &active {
+        let color = if alert.acknowledged { "#949cbb" } else { "#e78284" };
+        tooltip += &format!(
+            "<span foreground=\"{color}\">⚠ {}</span>\n",
+            crate::pango::escape(&alert.message)
+        );
+    }
+    if !active.is_empty() {
+        tooltip += "\n";
+    }
+    let alerting = active.iter().any(|a| !a.acknowledged);
+    let prices: Vec<String> = coins.iter().map(|c| currency.format_price(c.price)).collect();
+    let name_width = coins.iter().map(|c| c.name.chars().count()).max().unwrap_or(0);
+    let price_width = prices.iter().map(|p| p.chars().count()).max().unwrap_or(0);
+    let secondary_prices: Vec<Option<String>> = coins
+        .iter()
+        .map(|coin| {
+            let (cur, prices) = secondary.as_ref()?;
+            Some(cur.format_price(*prices.get(&coin.id)?.get(&cur.code)?))
+        })
+        .collect();
+    let secondary_width = secondary_prices
+        .iter()
+        .flatten()
+        .map(|p| p.chars().count())
         .max()
         .unwrap_or(0);
-    for (i, coin) in coins.iter().enumerate() {
+    if !options.changes.is_empty() && !coins.is_empty() {
+        // Labels over the change columns, which are 6 characters wide ("  -1.2%")
+        let labels: Vec<String> = options.changes.iter().map(|w| format!("{w: >5} ")).collect();
+        tooltip += &format!(
+            "<span foreground=\"#949cbb\">{}{}</span>\n",
+            " ".repeat(name_width + price_width + 8),
+            labels.join(" ").trim_end()
+        );
+    }
+    for ((coin, price), secondary_price) in coins.iter().zip(&prices).zip(&secondary_prices) {
         let change = coin.change.unwrap_or(0.0);
         let color = if change < 0.0 { "#e78284" } else { "#a6d189" };
-        // @NOTE: Store bitcoin price to display in the sidebar.
-        if coin.symbol == "btc" {
-            // @TODO: We have to do this, because of hardcoded color/emoji.
-            if i != 0 {
-                return Err("Bitcoin has to be at the very top for this to work...".into());
+        let name_pad = " ".repeat(name_width - coin.name.chars().count() + 3);
+        let price_pad = " ".repeat(price_width - price.chars().count() + 2);
+        let change_text = options
+            .changes
+            .iter()
+            .map(|window| match coin.change_over(window) {
+                Some(c) => {
+                    let color = if c < 0.0 { "#e78284" } else { "#a6d189" };
+                    format!("<span foreground=\"{color}\">{c: >5.1}%</span>")
+                }
+                None => "<span foreground=\"#949cbb\">  N/A </span>".to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let sparkline_text = match (options.sparkline, &coin.sparkline) {
+            (Some(width), Some(sparkline)) if !sparkline.price.is_empty() => {
+                format!("  {}", format_sparkline(&sparkline.price, width))
             }
-            text = format!(
-                "{text}<span foreground=\"{color}\" size=\"x-small\">{price:.1}k</span>",
-                price = coin.price / 1000.0
-            );
-        }
-        let coin_name = format!("  <b>{name}</b>:", name = crate::pango::escape(&coin.name));
-        let price_value = format!(
-            "$<span foreground=\"{color}\">{price:.precision$}</span>",
-            price = coin.price,
-            precision = 7_usize.saturating_sub(format!("${price}", price = coin.price.round()).len()),
-        );
-        let change_text = match coin.change {
-            Some(c) => format!(
-                "<span foreground=\"{color}\">{space}{c:.1}%</span>",
-                space = if c < 0.0 { "" } else { " " },
-            ),
-            None => "<span foreground=\"#949cbb\"> N/A</span>".to_string(),
+            (Some(width), _) => format!("  {}", " ".repeat(width)),
+            (None, _) => String::new(),
         };
-        tooltip += format!(
-            "{coin_name: <cname_len$}{price_value: <45}{change_text}\n",
-            cname_len = max_name_len + 10 + 3, // Adapt to coin name + markdown formatting + 3.
-        )
-        .as_ref();
+        let secondary_text = secondary_price
+            .as_ref()
+            .map(|p| {
+                format!(
+                    "  <span foreground=\"#949cbb\">{: >secondary_width$}</span>",
+                    crate::pango::escape(p)
+                )
+            })
+            .unwrap_or_default();
+        tooltip += &format!(
+            "  <b>{name}</b>:{name_pad}<span foreground=\"{color}\">{price}</span>{price_pad}{change_text}{sparkline_text}{secondary_text}\n",
+            name = crate::pango::escape(&coin.name),
+            price = crate::pango::escape(price),
+        );
+    }
+    if !missing.is_empty() {
+        tooltip += &format!(
+            "<span foreground=\"#949cbb\">  not found: {}</span>\n",
+            crate::pango::escape(&missing.join(", "))
+        );
+    }
+
+    tooltip += &portfolio_section;
+    if let Some(provider) = &wrapper.provider {
+        tooltip += &format!("\n<span foreground=\"#949cbb\">via {provider}</span>");
     }
 
     Ok(serde_json::to_string(&json!({
         "text": text,
         "tooltip": format!("<tt>{tooltip}</tt>"),
+        "class": if alerting { "alert" } else { "" },
     }))?)
 }
@@ -0,0 +1,167 @@
+use crate::crypto::currency::Currency;
+use crate::crypto::model::Coin;
+use crate::crypto::options::Holding;
+
+const GREEN: &str = "#a6d189";
+const RED: &str = "#e78284";
+const MUTED: &str = "#949cbb";
+
+/// Portfolio totals across all holdings with a known price.
+pub struct Totals {
+    pub value: f64,
+    pub change_24h: f64,  // in the quote currency
+    pub pnl: Option<f64>, // only over holdings with a cost basis
+    cost: f64,
+}
+
+impl Totals {
+    pub fn change_24h_pct(&self) -> f64 {
+        percent(self.change_24h, self.value - self.change_24h)
+    }
+}
+
+fn percent(part: f64, base: f64) -> f64 {
+    if base == 0.0 {
+        0.0
+    } else {
+        part / base * 100.0
+    }
+}
+
+fn direction_color(amount: f64) -> &'static str {
+    if amount < 0.0 {
+        RED
+    } else {
+        GREEN
+    }
+}
+
+/// Value lost/gained over 24h given the current value and the 24h percent change.
+fn change_24h(value: f64, change_pct: Option<f64>) -> f64 {
+    match change_pct {
+        Some(pct) => value - value / (1.0 + pct / 100.0),
+        None => 0.0,
+    }
+}
+
+pub fn totals(holdings: &[Holding], coins: &[Coin]) -> Totals {
+    let mut totals = Totals {
+        value: 0.0,
+        change_24h: 0.0,
+        pnl: None,
+        cost: 0.0,
+    };
+    for holding in holdings {
+        let Some(coin) = coins.iter().find(|c| c.id == holding.id) else {
+            continue;
+        };
+        let value = holding.amount * coin.price;
+        totals.value += value;
+        totals.change_24h += change_24h(value, coin.change);
+        if let Some(cost) = holding.cost {
+            totals.pnl = Some(totals.pnl.unwrap_or(0.0) + value - cost);
+            totals.cost += cost;
+        }
+    }
+    totals
+}
+
+struct Row {
+    name: String,
+    amount: String,
+    value: String,
+    cells: Vec<(String, &'static str)>, // 24h change, then P&L if there's a cost basis
+}
+
+/// "Portfolio" tooltip section: per-coin amount, value, 24h change and unrealized P&L, plus totals.
+pub fn render(holdings: &[Holding], coins: &[Coin], currency: &Currency) -> String {
+    let change_cell = |change: f64, pct: f64| {
+        (
+            format!("{} ({pct:+.1}%)", currency.format_change(change)),
+            direction_color(change),
+        )
+    };
+    let pnl_cell = |pnl: f64, cost: f64| {
+        (
+            format!("P&L {} ({:+.1}%)", currency.format_change(pnl), percent(pnl, cost)),
+            direction_color(pnl),
+        )
+    };
+
+    let mut rows = Vec::new();
+    for holding in holdings {
+        let coin = coins.iter().find(|c| c.id == holding.id);
+        let mut row = Row {
+            name: coin.map(|c| c.name.clone()).unwrap_or(holding.id.clone()),
+            amount: holding.amount.to_string(),
+            value: String::new(),
+            cells: Vec::new(),
+        };
+        match coin {
+            Some(coin) => {
+                let value = holding.amount * coin.price;
+                row.value = currency.format_amount(value);
+                row.cells
+                    .push(change_cell(change_24h(value, coin.change), coin.change.unwrap_or(0.0)));
+                if let Some(cost) = holding.cost {
+                    row.cells.push(pnl_cell(value - cost, cost));
+                }
+            }
+            None => row.cells.push(("no price".to_string(), MUTED)),
+        }
+        rows.push(row);
+    }
+
+    let totals = totals(holdings, coins);
+    let mut total = Row {
+        name: "Total".to_string(),
+        amount: String::new(),
+        value: currency.format_amount(totals.value),
+        cells: vec![change_cell(totals.change_24h, totals.change_24h_pct())],
+    };
+    if let Some(pnl) = totals.pnl {
+        total.cells.push(pnl_cell(pnl, totals.cost));
+    }
+
+    let width = |text: &str| text.chars().count();
+    let all = || rows.iter().chain(std::iter::once(&total));
+    let name_width = all().map(|r| width(&r.name)).max().unwrap_or(0);
+    let amount_width = all().map(|r| width(&r.amount)).max().unwrap_or(0);
+    let value_width = all().map(|r| width(&r.value)).max().unwrap_or(0);
+    let change_width = all()
+        .filter_map(|r| r.cells.first())
+        .map(|c| width(&c.0))
+        .max()
+        .unwrap_or(0);
+
+    let render_row = |row: &Row, bold: bool| {
+        let name_pad = " ".repeat(name_width - width(&row.name));
+        let name = crate::pango::escape(&row.name);
+        let name = if bold { format!("<b>{name}</b>") } else { name };
+        let mut line = format!(
+            "  {name}{name_pad}  {: >amount_width$}  {: >value_width$}",
+            row.amount,
+            crate::pango::escape(&row.value)
+        );
+        for (i, (text, color)) in row.cells.iter().enumerate() {
+            // Pad the 24h column so P&L lines up
+            let pad = if i == 0 {
+                " ".repeat(change_width - width(text))
+            } else {
+                String::new()
+            };
+            line += &format!(
+                "  <span foreground=\"{color}\">{}</span>{pad}",
+                crate::pango::escape(text)
+            );
+        }
+        line.trim_end().to_string() + "\n"
+    };
+
+    let mut out = "\n<span size=\"large\">Portfolio</span>\n".to_string();
+    for row in &rows {
+        out += &render_row(row, false);
+    }
+    out += &render_row(&total, true);
+    out
+}
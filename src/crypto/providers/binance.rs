@@ -0,0 +1,74 @@
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use serde_aux::prelude::*;
+use serde_json::from_value;
+
+use super::{get_json, ticker_for, PriceProvider, PriceRequest};
+use crate::crypto::model::Coin;
+
+/// Binance public 24h ticker. No key needed, but only knows exchange pairs: coins are mapped to
+/// tickers (see `ticker_for`), and the quote currency has to be traded against them.
+pub struct Binance;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Ticker {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    last_price: f64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    price_change_percent: f64,
+}
+
+/// Binance quote asset for a currency. Dollar prices come from the USDT pairs, which is what the
+/// exchange actually trades.
+fn quote_asset(code: &str) -> String {
+    match code {
+        "usd" => "USDT".to_string(),
+        code => code.to_uppercase(),
+    }
+}
+
+impl PriceProvider for Binance {
+    fn name(&self) -> &'static str {
+        "binance"
+    }
+
+    // Well under the 6000 weight/minute IP limit (a single-symbol ticker costs 2)
+    fn default_budget(&self) -> usize {
+        60
+    }
+
+    // @NOTE: One request per pair: `symbols=[…]` rejects the whole batch if any pair doesn't
+    //   exist, and a missing pair should only cost us that coin.
+    fn fetch(&self, client: &Client, request: &PriceRequest) -> Option<Vec<Coin>> {
+        let Some(ids) = request.ids else {
+            eprintln!("Binance has no market-cap ranking, it needs WAYBAP_CRYPTO_COINS");
+            return None;
+        };
+        let quote = quote_asset(&request.currency.code);
+        let mut coins = Vec::new();
+        for id in ids {
+            let Some((ticker, name)) = ticker_for(id) else {
+                continue;
+            };
+            let (price, change) = if ticker == quote {
+                (1.0, 0.0)
+            } else {
+                let url = format!("https://api.binance.com/api/v3/ticker/24hr?symbol={ticker}{quote}");
+                let Some(Ok(t)) = get_json(self, client.get(url)).map(from_value::<Ticker>) else {
+                    continue;
+                };
+                (t.last_price, t.price_change_percent)
+            };
+            coins.push(Coin {
+                id: id.clone(),
+                name,
+                symbol: ticker.to_lowercase(),
+                price,
+                change: Some(change),
+                ..Default::default()
+            });
+        }
+        Some(coins)
+    }
+}
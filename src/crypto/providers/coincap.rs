@@ -0,0 +1,94 @@
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use serde_aux::prelude::*;
+use serde_json::from_value;
+
+use super::{get_json, PriceProvider, PriceRequest};
+use crate::crypto::model::Coin;
+
+/// CoinCap v3 `/assets`. Needs a (free) key in WAYBAP_COINCAP_API_KEY and only quotes USD.
+/// Its asset ids are slugs like CoinGecko's, with a few exceptions mapped below.
+pub struct CoinCap;
+
+// (CoinGecko id, CoinCap id) where they differ
+const ID_MAP: &[(&str, &str)] = &[
+    ("ripple", "xrp"),
+    ("binancecoin", "binance-coin"),
+    ("avalanche-2", "avalanche"),
+    ("matic-network", "polygon"),
+    ("the-open-network", "toncoin"),
+];
+
+fn to_coincap(id: &str) -> &str {
+    ID_MAP.iter().find(|(gecko, _)| *gecko == id).map_or(id, |(_, cap)| cap)
+}
+
+fn from_coincap(id: &str) -> &str {
+    ID_MAP.iter().find(|(_, cap)| *cap == id).map_or(id, |(gecko, _)| gecko)
+}
+
+#[derive(Deserialize)]
+struct Assets {
+    data: Vec<Asset>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Asset {
+    id: String,
+    name: String,
+    symbol: String,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    price_usd: f64,
+    #[serde(default, deserialize_with = "deserialize_option_number_from_string")]
+    change_percent24_hr: Option<f64>,
+}
+
+impl PriceProvider for CoinCap {
+    fn name(&self) -> &'static str {
+        "coincap"
+    }
+
+    fn default_budget(&self) -> usize {
+        30
+    }
+
+    fn fetch(&self, client: &Client, request: &PriceRequest) -> Option<Vec<Coin>> {
+        let Ok(key) = std::env::var("WAYBAP_COINCAP_API_KEY") else {
+            eprintln!("CoinCap needs WAYBAP_COINCAP_API_KEY");
+            return None;
+        };
+        if request.currency.code != "usd" {
+            eprintln!("CoinCap only quotes usd, not '{}'", request.currency.code);
+            return None;
+        }
+        let url = match request.ids {
+            Some(ids) => format!(
+                "https://rest.coincap.io/v3/assets?ids={}",
+                ids.iter().map(|id| to_coincap(id)).collect::<Vec<_>>().join(",")
+            ),
+            None => "https://rest.coincap.io/v3/assets?limit=10".to_string(),
+        };
+        let response = get_json(self, client.get(url).bearer_auth(key.trim()))?;
+        let assets = match from_value::<Assets>(response) {
+            Ok(assets) => assets.data,
+            Err(err) => {
+                eprintln!("Failed to parse CoinCap assets: {err}");
+                return None;
+            }
+        };
+        Some(
+            assets
+                .into_iter()
+                .map(|asset| Coin {
+                    id: from_coincap(&asset.id).to_string(),
+                    name: asset.name,
+                    symbol: asset.symbol.to_lowercase(),
+                    price: asset.price_usd,
+                    change: asset.change_percent24_hr,
+                    ..Default::default()
+                })
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,86 @@
+use reqwest::blocking::{Client, RequestBuilder};
+use serde_json::from_value;
+
+use super::{get_json, PriceProvider, PriceRequest};
+use crate::crypto::model::Coin;
+
+/// CoinGecko `/coins/markets`. Works anonymously, but that shares a small rate limit with everyone
+/// behind the same IP; WAYBAP_COINGECKO_API_KEY with WAYBAP_COINGECKO_PLAN=demo|pro uses an account.
+pub struct CoinGecko;
+
+enum Plan {
+    Anonymous,
+    Demo(String),
+    Pro(String),
+}
+
+fn plan() -> Plan {
+    let key = std::env::var("WAYBAP_COINGECKO_API_KEY")
+        .ok()
+        .map(|k| k.trim().to_string())
+        .filter(|k| !k.is_empty());
+    let Some(key) = key else {
+        return Plan::Anonymous;
+    };
+    match std::env::var("WAYBAP_COINGECKO_PLAN").as_deref().map(str::trim) {
+        Ok("pro") => Plan::Pro(key),
+        Ok("demo") | Err(_) => Plan::Demo(key),
+        Ok(other) => {
+            eprintln!("WAYBAP_COINGECKO_PLAN: unknown plan '{other}' (known: demo, pro), using demo");
+            Plan::Demo(key)
+        }
+    }
+}
+
+/// GET `path` (e.g. "/search") on the API base for the configured plan, with its key header.
+pub fn request(client: &Client, path: &str) -> RequestBuilder {
+    match plan() {
+        // We have to use this custom header, because cloudflare blocks default one.
+        Plan::Anonymous => client
+            .get(format!("https://api.coingecko.com/api/v3{path}"))
+            .header("User-Agent", "curl/8.6.0"),
+        Plan::Demo(key) => client
+            .get(format!("https://api.coingecko.com/api/v3{path}"))
+            .header("x-cg-demo-api-key", key),
+        Plan::Pro(key) => client
+            .get(format!("https://pro-api.coingecko.com/api/v3{path}"))
+            .header("x-cg-pro-api-key", key),
+    }
+}
+
+impl PriceProvider for CoinGecko {
+    fn name(&self) -> &'static str {
+        "coingecko"
+    }
+
+    // Published limits: ~5-15/min anonymous, 30/min demo, 500+/min pro
+    fn default_budget(&self) -> usize {
+        match plan() {
+            Plan::Anonymous => 5,
+            Plan::Demo(_) => 30,
+            Plan::Pro(_) => 250,
+        }
+    }
+
+    fn fetch(&self, client: &Client, request: &PriceRequest) -> Option<Vec<Coin>> {
+        let vs_currency = &request.currency.code;
+        let params = match request.ids {
+            // @NOTE: `ids=` alone still paginates (100 per page by default), so per_page has to cover the list.
+            Some(ids) => format!("vs_currency={vs_currency}&ids={}&per_page={}", ids.join(","), ids.len()),
+            None => format!("vs_currency={vs_currency}&order=market_cap_desc&per_page=10"),
+        };
+        let url = format!(
+            "/coins/markets?{params}&price_change_percentage={}&sparkline={}",
+            request.windows.join(","),
+            request.sparkline
+        );
+        let coins = get_json(self, self::request(client, &url))?;
+        match from_value(coins) {
+            Ok(coins) => Some(coins),
+            Err(err) => {
+                eprintln!("Failed to parse CoinGecko markets: {err}");
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use reqwest::blocking::Client;
+use serde_json::Value;
+
+use super::{get_json, ticker_for, PriceProvider, PriceRequest};
+use crate::crypto::model::Coin;
+
+/// Kraken public ticker. No key needed; like Binance it only knows exchange pairs.
+pub struct Kraken;
+
+/// Kraken's own names for a few assets.
+fn kraken_asset(ticker: &str) -> &str {
+    match ticker {
+        "BTC" => "XBT",
+        "DOGE" => "XDG",
+        other => other,
+    }
+}
+
+/// Quote currencies Kraken has fiat/crypto pairs for.
+fn quote_asset(code: &str) -> Option<&'static str> {
+    Some(match code {
+        "usd" => "USD",
+        "eur" => "EUR",
+        "gbp" => "GBP",
+        "cad" => "CAD",
+        "aud" => "AUD",
+        "chf" => "CHF",
+        "jpy" => "JPY",
+        "btc" => "XBT",
+        "eth" => "ETH",
+        _ => return None,
+    })
+}
+
+impl PriceProvider for Kraken {
+    fn name(&self) -> &'static str {
+        "kraken"
+    }
+
+    // Public endpoints allow about one call per second
+    fn default_budget(&self) -> usize {
+        30
+    }
+
+    // @NOTE: One request per pair, for the same reason as Binance: an unknown pair fails the batch.
+    //   Kraken has no rolling 24h change, so the change is since today's open (00:00 UTC).
+    fn fetch(&self, client: &Client, request: &PriceRequest) -> Option<Vec<Coin>> {
+        let Some(ids) = request.ids else {
+            eprintln!("Kraken has no market-cap ranking, it needs WAYBAP_CRYPTO_COINS");
+            return None;
+        };
+        let Some(quote) = quote_asset(&request.currency.code) else {
+            eprintln!("Kraken has no pairs quoted in '{}'", request.currency.code);
+            return None;
+        };
+        let mut coins = Vec::new();
+        for id in ids {
+            let Some((ticker, name)) = ticker_for(id) else {
+                continue;
+            };
+            let base = kraken_asset(&ticker);
+            let (price, change) = if base == quote {
+                (1.0, 0.0)
+            } else {
+                let url = format!("https://api.kraken.com/0/public/Ticker?pair={base}{quote}");
+                let Some(response) = get_json(self, client.get(url)) else {
+                    continue;
+                };
+                // The result is keyed by Kraken's canonical pair name ("XXBTZUSD"), so take the only entry
+                let Some(pair) = response["result"].as_object().and_then(|r| r.values().next()) else {
+                    continue;
+                };
+                let number = |v: &Value| v.as_str().and_then(|s| s.parse::<f64>().ok());
+                let (Some(last), Some(open)) = (number(&pair["c"][0]), number(&pair["o"])) else {
+                    continue;
+                };
+                let change = if open > 0.0 { (last - open) / open * 100.0 } else { 0.0 };
+                (last, change)
+            };
+            coins.push(Coin {
+                id: id.clone(),
+                name,
+                symbol: ticker.to_lowercase(),
+                price,
+                change: Some(change),
+                ..Default::default()
+            });
+        }
+        Some(coins)
+    }
+}
@@ -0,0 +1,223 @@
+//! Market-data backends. Each provider fetches its own API and normalizes into `Coin`s;
+//! `query()` tries them in the order configured by WAYBAP_CRYPTO_PROVIDERS.
+mod binance;
+mod coincap;
+pub mod coingecko;
+mod kraken;
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use reqwest::blocking::{Client, RequestBuilder};
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use crate::config::env_list;
+use crate::crypto::currency::Currency;
+use crate::crypto::model::Coin;
+
+/// What to fetch, independent of the provider.
+pub struct PriceRequest<'a> {
+    pub ids: Option<&'a [String]>, // CoinGecko ids; None = top 10 by market cap
+    pub currency: &'a Currency,
+    pub windows: &'a [&'a str], // change windows, from CHANGE_WINDOWS
+    pub sparkline: bool,
+}
+
+pub trait PriceProvider {
+    /// Id used in WAYBAP_CRYPTO_PROVIDERS and shown in the tooltip footer.
+    fn name(&self) -> &'static str;
+    /// Requests per minute we allow ourselves, unless overridden by WAYBAP_CRYPTO_RATE_BUDGET.
+    fn default_budget(&self) -> usize;
+    /// `None` when the provider can't serve the request (unsupported currency, no watchlist, …)
+    /// or the API failed, so the next provider is tried.
+    fn fetch(&self, client: &Client, request: &PriceRequest) -> Option<Vec<Coin>>;
+}
+
+const DEFAULT_ORDER: &[&str] = &["coingecko", "binance", "kraken"];
+const ALL_PROVIDERS: &[&str] = &["coingecko", "binance", "kraken", "coincap"];
+
+fn provider_by_name(name: &str) -> Option<Box<dyn PriceProvider>> {
+    match name {
+        "coingecko" => Some(Box::new(coingecko::CoinGecko)),
+        "binance" => Some(Box::new(binance::Binance)),
+        "kraken" => Some(Box::new(kraken::Kraken)),
+        "coincap" => Some(Box::new(coincap::CoinCap)),
+        _ => None,
+    }
+}
+
+/// Providers in failover order.
+pub fn providers_from_env() -> Vec<Box<dyn PriceProvider>> {
+    let names =
+        env_list("WAYBAP_CRYPTO_PROVIDERS").unwrap_or_else(|| DEFAULT_ORDER.iter().map(|n| n.to_string()).collect());
+    let providers: Vec<_> = names
+        .iter()
+        .filter_map(|name| {
+            let provider = provider_by_name(name);
+            if provider.is_none() {
+                eprintln!(
+                    "WAYBAP_CRYPTO_PROVIDERS: unknown provider '{name}' (known: {})",
+                    ALL_PROVIDERS.join(", ")
+                );
+            }
+            provider
+        })
+        .collect();
+    if providers.is_empty() {
+        eprintln!("WAYBAP_CRYPTO_PROVIDERS has no valid providers, using default order");
+        return DEFAULT_ORDER.iter().filter_map(|n| provider_by_name(n)).collect();
+    }
+    providers
+}
+
+/// Try providers in order; the first one that returns coins wins.
+pub fn fetch(client: &Client, request: &PriceRequest) -> Option<(&'static str, Vec<Coin>)> {
+    providers_from_env().iter().find_map(|provider| {
+        let coins = provider.fetch(client, request).filter(|coins| !coins.is_empty());
+        if coins.is_none() {
+            eprintln!("Crypto provider '{}' failed, trying the next one", provider.name());
+        }
+        coins.map(|coins| (provider.name(), coins))
+    })
+}
+
+// @NOTE: Rate budgets are per process: the daemon's scheduler retries and secondary-currency calls
+//   all count against them. A provider over budget, or backing off after a 429, is skipped so the
+//   next one serves the request instead of us hammering an API that already said no.
+struct Budget {
+    recent: Vec<Instant>, // requests in the last minute
+    blocked_until: Option<Instant>,
+}
+
+static BUDGETS: Mutex<Option<HashMap<&'static str, Budget>>> = Mutex::new(None);
+
+fn budget_limit(name: &str, default: usize) -> usize {
+    env_list("WAYBAP_CRYPTO_RATE_BUDGET")
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|entry| entry.split_once(':'))
+        .find(|(provider, _)| provider.trim() == name)
+        .and_then(|(_, limit)| limit.trim().parse().ok())
+        .unwrap_or(default)
+}
+
+/// Record one request against `name`'s budget; false if it's exhausted or backing off.
+fn take_budget(name: &'static str, default: usize) -> bool {
+    let limit = budget_limit(name, default);
+    let mut budgets = BUDGETS.lock().unwrap();
+    let budget = budgets.get_or_insert_with(HashMap::new).entry(name).or_insert(Budget {
+        recent: Vec::new(),
+        blocked_until: None,
+    });
+    let now = Instant::now();
+    if let Some(until) = budget.blocked_until {
+        if now < until {
+            eprintln!(
+                "Crypto provider '{name}' is rate limited for another {}s, skipping",
+                (until - now).as_secs()
+            );
+            return false;
+        }
+        budget.blocked_until = None;
+    }
+    budget
+        .recent
+        .retain(|t| now.duration_since(*t) < Duration::from_secs(60));
+    if budget.recent.len() >= limit {
+        eprintln!("Crypto provider '{name}' is over its budget of {limit} requests/minute, skipping");
+        return false;
+    }
+    budget.recent.push(now);
+    true
+}
+
+/// Back off from `name` after a 429, for Retry-After seconds or a minute.
+fn block(name: &'static str, seconds: u64) {
+    let mut budgets = BUDGETS.lock().unwrap();
+    if let Some(budget) = budgets.as_mut().and_then(|b| b.get_mut(name)) {
+        budget.blocked_until = Some(Instant::now() + Duration::from_secs(seconds));
+    }
+}
+
+/// Send a request for `provider` and parse the JSON body. Every request counts against the
+/// provider's budget; a 429 puts it in back-off.
+fn get_json(provider: &dyn PriceProvider, request: RequestBuilder) -> Option<Value> {
+    let name = provider.name();
+    if !take_budget(name, provider.default_budget()) {
+        return None;
+    }
+    match request.send() {
+        Ok(response) => {
+            if response.status() == StatusCode::TOO_MANY_REQUESTS {
+                let retry_after = response
+                    .headers()
+                    .get("Retry-After")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(60);
+                eprintln!("Crypto provider '{name}' rate limited us, backing off for {retry_after}s");
+                block(name, retry_after);
+                return None;
+            }
+            if !response.status().is_success() {
+                eprintln!(
+                    "Crypto provider '{name}' returned non-success status: {}!",
+                    response.status()
+                );
+                return None;
+            }
+            match response.text().map(|text| serde_json::from_str(&text)) {
+                Ok(Ok(value)) => Some(value),
+                Ok(Err(err)) => {
+                    eprintln!("Failed to parse {name} JSON: {err}");
+                    None
+                }
+                Err(err) => {
+                    eprintln!("{name} response read failed: {err}!");
+                    None
+                }
+            }
+        }
+        Err(err) => {
+            eprintln!("{name} request failed: {err}!");
+            None
+        }
+    }
+}
+
+// CoinGecko id, exchange ticker and display name for exchanges that only know tickers.
+const TICKERS: &[(&str, &str, &str)] = &[
+    ("bitcoin", "BTC", "Bitcoin"),
+    ("ethereum", "ETH", "Ethereum"),
+    ("tether", "USDT", "Tether"),
+    ("usd-coin", "USDC", "USDC"),
+    ("binancecoin", "BNB", "BNB"),
+    ("solana", "SOL", "Solana"),
+    ("ripple", "XRP", "XRP"),
+    ("cardano", "ADA", "Cardano"),
+    ("dogecoin", "DOGE", "Dogecoin"),
+    ("tron", "TRX", "TRON"),
+    ("the-open-network", "TON", "Toncoin"),
+    ("avalanche-2", "AVAX", "Avalanche"),
+    ("polkadot", "DOT", "Polkadot"),
+    ("chainlink", "LINK", "Chainlink"),
+    ("litecoin", "LTC", "Litecoin"),
+    ("monero", "XMR", "Monero"),
+];
+
+/// Ticker and name for a CoinGecko id, for ticker-based exchanges. WAYBAP_CRYPTO_TICKERS
+/// (`id:TICKER`) adds coins or overrides the built-in table.
+fn ticker_for(id: &str) -> Option<(String, String)> {
+    let configured = env_list("WAYBAP_CRYPTO_TICKERS")
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|entry| entry.split_once(':'))
+        .find(|(coin, _)| coin.trim() == id)
+        .map(|(_, ticker)| ticker.trim().to_uppercase());
+    let known = TICKERS.iter().find(|(coin, _, _)| *coin == id);
+    let ticker = configured.or_else(|| known.map(|(_, ticker, _)| ticker.to_string()))?;
+    let name = known.map(|(_, _, name)| name.to_string()).unwrap_or(id.to_string());
+    Some((ticker, name))
+}
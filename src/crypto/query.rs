@@ -1,39 +1,89 @@
 use core::time::Duration;
+use std::collections::HashMap;
+
 use reqwest::blocking::Client;
+use serde_json::json;
 
-pub fn query() -> Option<String> {
-    let client = match Client::builder()
+use crate::crypto::alerts;
+use crate::crypto::options::CryptoOptions;
+use crate::crypto::providers::{self, PriceRequest};
+
+pub fn client() -> Option<Client> {
+    match Client::builder()
         .timeout(Duration::from_secs(5))
-        // We have to use this custom header, because cloudflare blocks default one.
-        .user_agent("curl/8.6.0")
+        .user_agent("waybap/0.1.0")
         .build()
     {
-        Ok(c) => c,
+        Ok(c) => Some(c),
         Err(e) => {
-            eprintln!("Failed to build HTTP client for CoinGecko: {e}");
-            return None;
+            eprintln!("Failed to build HTTP client for crypto prices: {e}");
+            None
         }
+    }
+}
+
+pub fn query() -> Option<String> {
+    let client = client()?;
+    let options = CryptoOptions::from_env();
+    let ids = options.fetch_ids();
+    let windows = options.fetch_windows();
+
+    let request = PriceRequest {
+        ids: ids.as_deref(),
+        currency: &options.currency,
+        windows: &windows,
+        sparkline: options.sparkline.is_some(),
     };
+    let (provider, mut coins) = providers::fetch(&client, &request)?;
 
-    let params = "vs_currency=usd&order=market_cap_desc&per_page=10&price_change_percentage=24h";
-    let crypto_url = format!("https://api.coingecko.com/api/v3/coins/markets?{params}");
-    match client.get(crypto_url).send() {
-        Ok(response) => {
-            if !response.status().is_success() {
-                eprintln!("Request returned non-success status: {}!", response.status());
-                return None;
-            }
-            match response.text() {
-                Ok(text) => Some(text),
-                Err(err) => {
-                    eprintln!("Request text read failed: {err}!");
-                    None
-                }
+    // Top-10 mode: bar coins, holdings and alert coins outside the top 10 need an `ids=` request.
+    // They are cached apart from `coins` so they stay out of the price table.
+    let top = coins.len();
+    if ids.is_none() {
+        let missing = options.missing_ids(&coins);
+        if !missing.is_empty() {
+            let request = PriceRequest {
+                ids: Some(&missing),
+                ..request
+            };
+            match providers::fetch(&client, &request) {
+                Some((_, extra)) => coins.extend(extra),
+                None => eprintln!("Failed to fetch coins outside the top 10: {}", missing.join(", ")),
             }
         }
-        Err(err) => {
-            eprintln!("CoinGecko request failed: {err}!");
-            None
-        }
     }
+
+    if !options.alerts.is_empty() {
+        alerts::evaluate(&options.alerts, &coins, &options.currency);
+    }
+
+    // Secondary currency: prices for the coins we just got, from whichever provider can quote it.
+    // A failure here only drops the extra column.
+    let secondary = options.secondary_currency.as_ref().and_then(|currency| {
+        let ids: Vec<String> = coins.iter().map(|c| c.id.clone()).collect();
+        let request = PriceRequest {
+            ids: Some(&ids),
+            currency,
+            windows: &["24h"],
+            sparkline: false,
+        };
+        let (_, quoted) = providers::fetch(&client, &request)?;
+        let prices: HashMap<String, HashMap<String, f64>> = quoted
+            .into_iter()
+            .map(|c| (c.id, HashMap::from([(currency.code.clone(), c.price)])))
+            .collect();
+        Some(json!({ "currency": currency.code, "prices": prices }))
+    });
+
+    let extra = coins.split_off(top);
+    Some(
+        json!({
+            "currency": options.currency.code,
+            "provider": provider,
+            "coins": coins,
+            "extra": extra,
+            "secondary": secondary,
+        })
+        .to_string(),
+    )
 }
@@ -0,0 +1,51 @@
+use serde::Deserialize;
+
+use crate::crypto::providers::coingecko;
+use crate::crypto::query::client;
+
+#[derive(Deserialize)]
+struct SearchResponse {
+    coins: Vec<SearchCoin>,
+}
+
+#[derive(Deserialize)]
+struct SearchCoin {
+    id: String,
+    name: String,
+    symbol: String,
+    market_cap_rank: Option<u32>,
+}
+
+/// `waybap crypto search <term>`: print CoinGecko ids matching a name or ticker, for WAYBAP_CRYPTO_COINS.
+pub fn search(term: &str) -> Result<(), ()> {
+    let client = client().ok_or(())?;
+    let response = coingecko::request(&client, "/search")
+        .query(&[("query", term)])
+        .send()
+        .map_err(|err| eprintln!("ERROR: CoinGecko search failed: {err}"))?;
+    if !response.status().is_success() {
+        eprintln!("ERROR: CoinGecko search returned {}", response.status());
+        return Err(());
+    }
+    let body = response
+        .text()
+        .map_err(|err| eprintln!("ERROR: failed to read CoinGecko search response: {err}"))?;
+    let results = serde_json::from_str::<SearchResponse>(&body)
+        .map_err(|err| eprintln!("ERROR: failed to parse CoinGecko search response: {err}"))?;
+
+    if results.coins.is_empty() {
+        eprintln!("No coins found for '{term}'");
+        return Err(());
+    }
+    println!("{:<32} {:<10} {:>6}  NAME", "ID", "SYMBOL", "RANK");
+    for coin in results.coins.iter().take(20) {
+        let rank = coin.market_cap_rank.map(|r| r.to_string()).unwrap_or("-".to_string());
+        println!(
+            "{:<32} {:<10} {rank:>6}  {}",
+            coin.id,
+            coin.symbol.to_uppercase(),
+            coin.name
+        );
+    }
+    Ok(())
+}
@@ -2,7 +2,9 @@ use std::env;
 use std::fs::read_to_string;
 use std::process::ExitCode;
 
+mod config;
 mod crypto;
+mod markets;
 mod pango;
 mod scheduler;
 mod sensors;
@@ -14,12 +16,17 @@ fn help_text(program: &str) {
     eprintln!("Usage: {program} [SUBCOMMAND] [OPTIONS]");
     eprintln!("Subcommands:");
     eprintln!("    serve [address]       start the daemon (default: 127.0.0.1:6969)");
-    eprintln!("    test <weather|crypto|sensors|usage> [--cache] fetch and parse live data (or cached)");
+    eprintln!("    test <weather|crypto|markets|sensors|usage> [--cache] fetch and parse live data (or cached)");
+    eprintln!("    crypto search <term>  find CoinGecko coin ids for WAYBAP_CRYPTO_COINS");
+    eprintln!("    sensors snapshot <dir> copy the hwmon and DRM sysfs files into <dir> (read back with WAYBAP_SENSORS_SYSFS_ROOT)");
 }
 
 fn start_scheduler() {
-    scheduler::Job::new("weather", 60 * 10, weather::query).run();
+    scheduler::Job::new("weather", 60 * 10, weather::query)
+        .stale_unless(weather::cache_is_current)
+        .run();
     scheduler::Job::new("crypto", 60 * 15, crypto::query).run();
+    scheduler::Job::new("markets", 60 * 5, markets::query).run();
     scheduler::Job::new("sensors", 1, sensors::query).run();
     scheduler::Job::new("usage", 120, usage::query).run();
 }
@@ -69,12 +76,13 @@ fn entry() -> Result<(), ()> {
         "test" => {
             let target = args.next().ok_or_else(|| {
                 help_text(&program);
-                eprintln!("ERROR: 'test' requires a target: weather, crypto, sensors, or usage");
+                eprintln!("ERROR: 'test' requires a target: weather, crypto, markets, sensors, or usage");
             })?;
             let use_cache = args.next().map(|a| a == "--cache").unwrap_or(false);
             match target.as_str() {
                 "weather" => run_query("weather", use_cache, weather::query, weather::parse_data),
                 "crypto" => run_query("crypto", use_cache, crypto::query, crypto::parse_data),
+                "markets" => run_query("markets", use_cache, markets::query, markets::parse_data),
                 "sensors" => run_query("sensors", use_cache, sensors::query, sensors::parse_data),
                 "usage" => run_query("usage", use_cache, usage::query, usage::parse_data),
                 _ => {
@@ -85,6 +93,24 @@ fn entry() -> Result<(), ()> {
             }
         }
 
+        "crypto" => match (args.next().as_deref(), args.next()) {
+            (Some("search"), Some(term)) => crypto::search(&term),
+            _ => {
+                help_text(&program);
+                eprintln!("ERROR: usage: crypto search <term>");
+                Err(())
+            }
+        },
+
+        "sensors" => match (args.next().as_deref(), args.next()) {
+            (Some("snapshot"), Some(dir)) => sensors::snapshot(&dir),
+            _ => {
+                help_text(&program);
+                eprintln!("ERROR: usage: sensors snapshot <dir>");
+                Err(())
+            }
+        },
+
         _ => {
             help_text(&program);
             eprintln!("ERROR: unknown subcommand {subcommand}");
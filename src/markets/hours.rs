@@ -0,0 +1,108 @@
+//! Regular trading sessions, to show whether a quote's market is open right now. Computed
+//! locally from fixed hours and DST rules; exchange holidays and half days aren't known.
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Weekday};
+
+#[derive(Clone, Copy)]
+pub enum Market {
+    NewYork,
+    London,
+    Xetra,
+    Warsaw,
+    Tokyo,
+    Fx,
+}
+
+#[derive(Clone, Copy)]
+enum Dst {
+    None,
+    Us, // second Sunday in March to first Sunday in November, at 2:00 local
+    Eu, // last Sunday in March to last Sunday in October, at 1:00 UTC
+}
+
+impl Market {
+    /// Market for a Stooq-style symbol, by suffix or index name. `None` when we don't know it.
+    pub fn for_symbol(symbol: &str) -> Option<Market> {
+        if symbol.contains('/') {
+            return Some(Market::Fx);
+        }
+        let market = match symbol.rsplit_once('.').map(|(_, suffix)| suffix) {
+            Some("us") => Market::NewYork,
+            Some("uk") => Market::London,
+            Some("de") => Market::Xetra,
+            Some("pl") => Market::Warsaw,
+            Some("jp") => Market::Tokyo,
+            Some(_) => return None,
+            None => match symbol {
+                "^spx" | "^dji" | "^ndq" | "^ndx" => Market::NewYork,
+                "^ukx" => Market::London,
+                "^dax" => Market::Xetra,
+                "^wig20" | "wig20" => Market::Warsaw,
+                "^nkx" => Market::Tokyo,
+                _ => return None,
+            },
+        };
+        Some(market)
+    }
+
+    // (standard UTC offset in hours, DST rule, session open, session close)
+    fn session(self) -> (i64, Dst, (u32, u32), (u32, u32)) {
+        match self {
+            Market::NewYork => (-5, Dst::Us, (9, 30), (16, 0)),
+            Market::London => (0, Dst::Eu, (8, 0), (16, 30)),
+            Market::Xetra => (1, Dst::Eu, (9, 0), (17, 30)),
+            Market::Warsaw => (1, Dst::Eu, (9, 0), (17, 0)),
+            Market::Tokyo => (9, Dst::None, (9, 0), (15, 30)),
+            Market::Fx => (0, Dst::None, (0, 0), (0, 0)), // see is_open
+        }
+    }
+
+    pub fn is_open(self, utc: NaiveDateTime) -> bool {
+        if let Market::Fx = self {
+            // @NOTE: FX trades around the clock from the Sydney open on Sunday to the New York close
+            //   on Friday, both roughly 22:00 UTC (an hour off during half of the year).
+            let hour_of_week = utc.weekday().num_days_from_monday() * 24 + utc.hour();
+            let (friday_close, sunday_open) = (4 * 24 + 22, 6 * 24 + 22);
+            return !(friday_close..sunday_open).contains(&hour_of_week);
+        }
+        let (offset, dst, open, close) = self.session();
+        let local = utc + Duration::hours(offset + if is_dst(dst, utc) { 1 } else { 0 });
+        let time = local.time();
+        let at = |(h, m): (u32, u32)| NaiveTime::from_hms_opt(h, m, 0).unwrap_or_default();
+        !matches!(local.weekday(), Weekday::Sat | Weekday::Sun) && time >= at(open) && time < at(close)
+    }
+}
+
+/// The `n`th Sunday of a month (1-based), or the last one with `n = 0`.
+fn sunday(year: i32, month: u32, n: u32) -> NaiveDate {
+    if n == 0 {
+        let next_month = if month == 12 {
+            NaiveDate::from_ymd_opt(year + 1, 1, 1)
+        } else {
+            NaiveDate::from_ymd_opt(year, month + 1, 1)
+        };
+        let last = next_month.unwrap_or_default() - Duration::days(1);
+        last - Duration::days(last.weekday().num_days_from_sunday() as i64)
+    } else {
+        NaiveDate::from_weekday_of_month_opt(year, month, Weekday::Sun, n as u8).unwrap_or_default()
+    }
+}
+
+fn is_dst(dst: Dst, utc: NaiveDateTime) -> bool {
+    let year = utc.year();
+    let (start, end) = match dst {
+        Dst::None => return false,
+        // 2:00 EST = 7:00 UTC; 2:00 EDT = 6:00 UTC
+        Dst::Us => (
+            sunday(year, 3, 2).and_hms_opt(7, 0, 0),
+            sunday(year, 11, 1).and_hms_opt(6, 0, 0),
+        ),
+        Dst::Eu => (
+            sunday(year, 3, 0).and_hms_opt(1, 0, 0),
+            sunday(year, 10, 0).and_hms_opt(1, 0, 0),
+        ),
+    };
+    match (start, end) {
+        (Some(start), Some(end)) => utc >= start && utc < end,
+        _ => false,
+    }
+}
@@ -0,0 +1,9 @@
+mod hours;
+mod model;
+mod options;
+mod parsing;
+mod providers;
+mod query;
+
+pub use parsing::parse_data;
+pub use query::query;
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// Last price for an equity, index, ETF or FX pair, normalized across providers.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Quote {
+    pub symbol: String, // as configured: "spy.us", "^spx", "EUR/USD"
+    pub name: String,
+    pub price: f64,
+    pub previous: Option<f64>, // previous close (or previous reference rate), for the day's change
+    pub date: Option<String>,  // trading day of `price`, "YYYY-MM-DD"
+    pub provider: String,
+}
+
+impl Quote {
+    /// Percent change against the previous close.
+    pub fn change(&self) -> Option<f64> {
+        let previous = self.previous.filter(|p| *p != 0.0)?;
+        Some((self.price - previous) / previous * 100.0)
+    }
+}
+
+/// An FX pair from WAYBAP_MARKETS_FX: price of one `base` in `quote`.
+#[derive(Clone, Debug)]
+pub struct FxPair {
+    pub base: String, // uppercase ISO code
+    pub quote: String,
+}
+
+impl FxPair {
+    /// Parse `EUR/USD` or `EURUSD`.
+    pub fn parse(entry: &str) -> Option<FxPair> {
+        let entry = entry.trim().to_uppercase();
+        let (base, quote) = match entry.split_once('/') {
+            Some((base, quote)) => (base.trim().to_string(), quote.trim().to_string()),
+            // Byte slicing is only safe on ASCII; "ÄÖÜ" is 6 bytes too
+            None if entry.len() == 6 && entry.is_ascii() => (entry[..3].to_string(), entry[3..].to_string()),
+            None => return None,
+        };
+        let is_code = |c: &str| c.len() == 3 && c.chars().all(|ch| ch.is_ascii_alphabetic());
+        (is_code(&base) && is_code(&quote)).then_some(FxPair { base, quote })
+    }
+
+    /// Display/cache symbol, "EUR/USD".
+    pub fn symbol(&self) -> String {
+        format!("{}/{}", self.base, self.quote)
+    }
+}
@@ -0,0 +1,47 @@
+use crate::config::env_list;
+use crate::markets::model::FxPair;
+
+/// Markets module configuration, read from WAYBAP_* env vars.
+pub struct MarketsOptions {
+    pub symbols: Vec<String>, // Stooq-style tickers in display order: "^spx", "spy.us", "vwce.de"
+    pub fx: Vec<FxPair>,
+    pub bar: Option<String>, // symbol or pair shown in the bar; None = first configured
+}
+
+impl MarketsOptions {
+    pub fn from_env() -> MarketsOptions {
+        let symbols = env_list("WAYBAP_MARKETS_SYMBOLS")
+            .unwrap_or_else(|| vec!["^spx".to_string(), "^ndq".to_string()])
+            .into_iter()
+            .map(|s| s.to_lowercase())
+            .collect();
+        let fx = env_list("WAYBAP_MARKETS_FX")
+            .unwrap_or_else(|| vec!["EUR/USD".to_string()])
+            .iter()
+            .filter_map(|entry| {
+                let pair = FxPair::parse(entry);
+                if pair.is_none() {
+                    eprintln!("WAYBAP_MARKETS_FX: invalid pair '{entry}' (expected e.g. EUR/USD)");
+                }
+                pair
+            })
+            .collect();
+        let bar = std::env::var("WAYBAP_MARKETS_BAR")
+            .ok()
+            .map(|b| b.trim().to_string())
+            .filter(|b| !b.is_empty());
+        MarketsOptions { symbols, fx, bar }
+    }
+
+    /// Which quote the bar shows: WAYBAP_MARKETS_BAR (a symbol or pair), else the first one configured.
+    pub fn bar_symbol(&self) -> Option<String> {
+        match &self.bar {
+            Some(bar) => Some(FxPair::parse(bar).map_or(bar.to_lowercase(), |pair| pair.symbol())),
+            None => self
+                .symbols
+                .first()
+                .cloned()
+                .or_else(|| self.fx.first().map(FxPair::symbol)),
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::{json, value::from_value, Value};
+
+use crate::markets::hours::Market;
+use crate::markets::model::{FxPair, Quote};
+use crate::markets::options::MarketsOptions;
+
+#[derive(Deserialize)]
+struct QueryWrapper {
+    quotes: Option<Vec<Quote>>, // None = no provider could serve them
+    fx: Option<Vec<Quote>>,
+}
+
+// Longer names (Stooq's are often "SPDR S&P 500 ETF TRUST") are cut to keep the table narrow.
+const NAME_WIDTH: usize = 24;
+
+fn short_name(name: &str) -> String {
+    if name.chars().count() > NAME_WIDTH {
+        name.chars().take(NAME_WIDTH - 1).collect::<String>() + "…"
+    } else {
+        name.to_string()
+    }
+}
+
+/// Tooltip price: 2 decimals for equities, ~6 significant digits for FX ("1.08234", "41.2950").
+fn format_price(price: f64, fx: bool) -> String {
+    if !fx {
+        return format!("{price:.2}");
+    }
+    let int_digits = if price.abs() >= 1.0 {
+        price.abs().log10().floor() as i32 + 1
+    } else {
+        1
+    };
+    let decimals = (6 - int_digits).max(2) as usize;
+    format!("{price:.decimals$}")
+}
+
+/// Short bar price: "5823", "18.4k", "1.0823".
+fn format_compact(price: f64) -> String {
+    let abs = price.abs();
+    if abs >= 100_000.0 {
+        format!("{:.1}k", price / 1e3)
+    } else if abs >= 100.0 {
+        format!("{price:.0}")
+    } else if abs >= 10.0 {
+        format!("{price:.2}")
+    } else {
+        format!("{price:.4}")
+    }
+}
+
+fn change_color(quote: &Quote) -> &'static str {
+    if quote.change().unwrap_or(0.0) < 0.0 {
+        "#e78284"
+    } else {
+        "#a6d189"
+    }
+}
+
+fn is_open(symbol: &str) -> Option<bool> {
+    Market::for_symbol(symbol).map(|market| market.is_open(Utc::now().naive_utc()))
+}
+
+/// `quotes` in the order of `symbols`, collecting symbols without a quote into `missing`.
+fn ordered<'a>(quotes: &'a Option<Vec<Quote>>, symbols: &[String], missing: &mut Vec<String>) -> Option<Vec<&'a Quote>> {
+    let quotes = quotes.as_ref()?;
+    Some(
+        symbols
+            .iter()
+            .filter_map(|symbol| {
+                let quote = quotes.iter().find(|q| q.symbol == *symbol);
+                if quote.is_none() {
+                    missing.push(symbol.clone());
+                }
+                quote
+            })
+            .collect(),
+    )
+}
+
+pub fn parse_data(raw_markets: Value) -> Result<String, Box<dyn std::error::Error>> {
+    let wrapper = from_value::<QueryWrapper>(raw_markets)?;
+    let options = MarketsOptions::from_env();
+    let fx_symbols: Vec<String> = options.fx.iter().map(FxPair::symbol).collect();
+
+    // Cached quotes in the configured order; anything configured but not returned is listed as missing.
+    // The cache may predate a config change, so quotes no longer configured are dropped.
+    let mut missing = Vec::new();
+    let quotes = ordered(&wrapper.quotes, &options.symbols, &mut missing);
+    let fx = ordered(&wrapper.fx, &fx_symbols, &mut missing);
+
+    // @NOTE: You can't put 'class' on the span here for some reason, but you
+    //        can change a bunch of things directly with this special subset
+    //        of html (bruh): https://docs.gtk.org/Pango/pango_markup.html
+    let bar_symbol = options.bar_symbol();
+    let bar_quote = quotes
+        .iter()
+        .chain(fx.iter())
+        .flatten()
+        .find(|q| Some(&q.symbol) == bar_symbol.as_ref());
+    let text = match bar_quote {
+        Some(quote) => {
+            let glyph = if quote.symbol.contains('/') {
+                "\u{F04E1}" // 󰓡 nf-md-swap_horizontal
+            } else {
+                "\u{F012A}" // 󰄪 nf-md-chart_line
+            };
+            format!(
+                "<span size=\"large\" foreground=\"#8caaee\"> {glyph}</span>\n<span foreground=\"{}\" size=\"x-small\">{}</span>",
+                change_color(quote),
+                format_compact(quote.price)
+            )
+        }
+        None => "<span size=\"large\" foreground=\"#8caaee\"> \u{F012A}</span>\n<span foreground=\"#949cbb\" size=\"x-small\">N/A</span>".to_string(),
+    };
+    // Dimmable with `#custom-markets.closed` when the bar quote's market is closed
+    let class = match bar_quote.and_then(|q| is_open(&q.symbol)) {
+        Some(false) => "closed",
+        _ => "",
+    };
+
+    // One alignment for both sections, so FX lines up under the equities
+    let all: Vec<&Quote> = quotes.iter().chain(fx.iter()).flatten().copied().collect();
+    let names: Vec<String> = all.iter().map(|q| short_name(&q.name)).collect();
+    let name_width = names.iter().map(|n| n.chars().count()).max().unwrap_or(0);
+    let price_width = all
+        .iter()
+        .map(|q| format_price(q.price, q.symbol.contains('/')).len())
+        .max()
+        .unwrap_or(0);
+    let row = |quote: &Quote| {
+        let name = short_name(&quote.name);
+        let price = format_price(quote.price, quote.symbol.contains('/'));
+        let color = change_color(quote);
+        let name_pad = " ".repeat(name_width - name.chars().count() + 3);
+        let price_pad = " ".repeat(price_width - price.len() + 2);
+        let change_text = match quote.change() {
+            Some(c) => format!("<span foreground=\"{color}\">{c: >6.2}%</span>"),
+            None => "<span foreground=\"#949cbb\">   N/A </span>".to_string(),
+        };
+        let state = match is_open(&quote.symbol) {
+            Some(true) => "  <span foreground=\"#a6d189\">open</span>",
+            Some(false) => "  <span foreground=\"#949cbb\">closed</span>",
+            None => "",
+        };
+        format!(
+            "  <b>{name}</b>:{name_pad}<span foreground=\"{color}\">{price}</span>{price_pad}{change_text}{state}\n",
+            name = crate::pango::escape(&name),
+        )
+    };
+
+    let mut tooltip = "<span size=\"xx-large\">Markets</span>\n".to_string();
+    match &quotes {
+        Some(quotes) => quotes.iter().for_each(|q| tooltip += &row(q)),
+        None => tooltip += "<span foreground=\"#949cbb\">  quotes unavailable</span>\n",
+    }
+    if !fx_symbols.is_empty() {
+        tooltip += "\n<span size=\"large\">FX</span>\n";
+        match &fx {
+            Some(fx) => fx.iter().for_each(|q| tooltip += &row(q)),
+            None => tooltip += "<span foreground=\"#949cbb\">  rates unavailable</span>\n",
+        }
+    }
+    if !missing.is_empty() {
+        tooltip += &format!(
+            "<span foreground=\"#949cbb\">  not found: {}</span>\n",
+            crate::pango::escape(&missing.join(", "))
+        );
+    }
+
+    let mut providers: Vec<&str> = all.iter().map(|q| q.provider.as_str()).collect();
+    providers.dedup();
+    if !providers.is_empty() {
+        tooltip += &format!("\n<span foreground=\"#949cbb\">via {}</span>", providers.join(", "));
+    }
+
+    Ok(serde_json::to_string(&json!({
+        "text": text,
+        "tooltip": format!("<tt>{tooltip}</tt>"),
+        "class": class,
+    }))?)
+}
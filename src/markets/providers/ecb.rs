@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use reqwest::blocking::Client;
+
+use super::{get_text, MarketProvider};
+use crate::markets::model::{FxPair, Quote};
+
+/// The ECB's own euro foreign exchange reference rates (XML). Everything is quoted against EUR,
+/// so other pairs are crossed through it.
+pub struct Ecb;
+
+/// One publication day: date and EUR -> currency rates (EUR itself included as 1).
+struct Day {
+    date: String,
+    rates: HashMap<String, f64>,
+}
+
+// @NOTE: The XML is a flat list of `<Cube time='…'>` days (newest first) holding
+//   `<Cube currency='USD' rate='1.0823'/>` entries, so plain string scanning does the job
+//   without pulling in an XML parser.
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let start = tag.find(&format!("{name}='"))? + name.len() + 2;
+    let len = tag[start..].find('\'')?;
+    Some(&tag[start..start + len])
+}
+
+fn parse_days(xml: &str) -> Vec<Day> {
+    xml.split("<Cube time=")
+        .skip(1)
+        .map(|chunk| {
+            let chunk = format!("time={chunk}");
+            let date = attribute(&chunk, "time").unwrap_or_default().to_string();
+            let mut rates: HashMap<String, f64> = chunk
+                .split("<Cube ")
+                .filter_map(|tag| {
+                    Some((
+                        attribute(tag, "currency")?.to_string(),
+                        attribute(tag, "rate")?.parse().ok()?,
+                    ))
+                })
+                .collect();
+            rates.insert("EUR".to_string(), 1.0);
+            Day { date, rates }
+        })
+        .collect()
+}
+
+fn cross(day: &Day, pair: &FxPair) -> Option<f64> {
+    Some(day.rates.get(&pair.quote)? / day.rates.get(&pair.base)?)
+}
+
+impl MarketProvider for Ecb {
+    fn name(&self) -> &'static str {
+        "ecb"
+    }
+
+    fn fx(&self, client: &Client, pairs: &[FxPair]) -> Option<Vec<Quote>> {
+        // The 90-day history is the smallest file that also has the previous day, for the change
+        let url = "https://www.ecb.europa.eu/stats/eurofxref/eurofxref-hist-90d.xml";
+        let days = parse_days(&get_text("ECB", client.get(url))?);
+        let (latest, previous) = (days.first()?, days.get(1));
+        Some(
+            pairs
+                .iter()
+                .filter_map(|pair| {
+                    Some(Quote {
+                        symbol: pair.symbol(),
+                        name: pair.symbol(),
+                        price: cross(latest, pair)?,
+                        previous: previous.and_then(|day| cross(day, pair)),
+                        date: Some(latest.date.clone()),
+                        provider: self.name().to_string(),
+                    })
+                })
+                .collect(),
+        )
+    }
+}
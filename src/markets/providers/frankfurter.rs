@@ -0,0 +1,77 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{Duration, Local};
+use reqwest::blocking::Client;
+use serde::Deserialize;
+
+use super::{get_text, MarketProvider};
+use crate::markets::model::{FxPair, Quote};
+
+/// Frankfurter (frankfurter.dev): ECB reference rates as JSON, with any base currency.
+/// Rates are published once per working day around 16:00 CET.
+pub struct Frankfurter;
+
+#[derive(Deserialize)]
+struct TimeSeries {
+    rates: BTreeMap<String, HashMap<String, f64>>, // date -> quote currency -> rate; BTreeMap keeps dates sorted
+}
+
+impl MarketProvider for Frankfurter {
+    fn name(&self) -> &'static str {
+        "frankfurter"
+    }
+
+    // @NOTE: A week-long time series rather than `/latest`, so we get the previous working day's
+    //   rate for the change too — across weekends and holidays. One request per base currency.
+    fn fx(&self, client: &Client, pairs: &[FxPair]) -> Option<Vec<Quote>> {
+        let start = (Local::now().date_naive() - Duration::days(7)).format("%Y-%m-%d");
+        let mut bases: Vec<&str> = pairs.iter().map(|p| p.base.as_str()).collect();
+        bases.sort();
+        bases.dedup();
+        let mut series = HashMap::new();
+        for base in bases {
+            let quotes: Vec<&str> = pairs
+                .iter()
+                .filter(|p| p.base == base)
+                .map(|p| p.quote.as_str())
+                .collect();
+            let request = client
+                .get(format!("https://api.frankfurter.dev/v1/{start}.."))
+                .query(&[("base", base), ("symbols", &quotes.join(","))]);
+            let Some(body) = get_text("Frankfurter", request) else {
+                continue;
+            };
+            match serde_json::from_str::<TimeSeries>(&body) {
+                Ok(ts) => {
+                    series.insert(base, ts.rates);
+                }
+                Err(err) => eprintln!("Failed to parse Frankfurter rates: {err}"),
+            }
+        }
+        if series.is_empty() {
+            return None;
+        }
+
+        Some(
+            pairs
+                .iter()
+                .filter_map(|pair| {
+                    let mut days = series
+                        .get(pair.base.as_str())?
+                        .iter()
+                        .rev()
+                        .filter_map(|(date, rates)| Some((date, *rates.get(&pair.quote)?)));
+                    let (date, price) = days.next()?;
+                    Some(Quote {
+                        symbol: pair.symbol(),
+                        name: pair.symbol(),
+                        price,
+                        previous: days.next().map(|(_, rate)| rate),
+                        date: Some(date.clone()),
+                        provider: self.name().to_string(),
+                    })
+                })
+                .collect(),
+        )
+    }
+}
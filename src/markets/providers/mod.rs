@@ -0,0 +1,81 @@
+//! Quote backends. Each provider fetches its own API and normalizes into `Quote`s; `query()` asks
+//! them in the order configured by WAYBAP_MARKETS_PROVIDERS, separately for equities and FX.
+mod ecb;
+mod frankfurter;
+mod stooq;
+
+use reqwest::blocking::{Client, RequestBuilder};
+
+use crate::config::env_list;
+use crate::markets::model::{FxPair, Quote};
+
+pub trait MarketProvider {
+    /// Id used in WAYBAP_MARKETS_PROVIDERS and shown in the tooltip footer.
+    fn name(&self) -> &'static str;
+    /// Equity/index/ETF quotes. `None` if unsupported or the API failed.
+    fn quotes(&self, _client: &Client, _symbols: &[String]) -> Option<Vec<Quote>> {
+        None
+    }
+    /// FX rates. `None` if unsupported or the API failed.
+    fn fx(&self, _client: &Client, _pairs: &[FxPair]) -> Option<Vec<Quote>> {
+        None
+    }
+}
+
+const DEFAULT_ORDER: &[&str] = &["stooq", "frankfurter", "ecb"];
+
+fn provider_by_name(name: &str) -> Option<Box<dyn MarketProvider>> {
+    match name {
+        "stooq" => Some(Box::new(stooq::Stooq)),
+        "frankfurter" => Some(Box::new(frankfurter::Frankfurter)),
+        "ecb" => Some(Box::new(ecb::Ecb)),
+        _ => None,
+    }
+}
+
+/// Providers in failover order.
+pub fn providers_from_env() -> Vec<Box<dyn MarketProvider>> {
+    let names =
+        env_list("WAYBAP_MARKETS_PROVIDERS").unwrap_or_else(|| DEFAULT_ORDER.iter().map(|n| n.to_string()).collect());
+    let providers: Vec<_> = names
+        .iter()
+        .filter_map(|name| {
+            let provider = provider_by_name(name);
+            if provider.is_none() {
+                eprintln!(
+                    "WAYBAP_MARKETS_PROVIDERS: unknown provider '{name}' (known: {})",
+                    DEFAULT_ORDER.join(", ")
+                );
+            }
+            provider
+        })
+        .collect();
+    if providers.is_empty() {
+        eprintln!("WAYBAP_MARKETS_PROVIDERS has no valid providers, using default order");
+        return DEFAULT_ORDER.iter().filter_map(|n| provider_by_name(n)).collect();
+    }
+    providers
+}
+
+/// Send a request and return the body, logging failures under the provider's name.
+fn get_text(name: &str, request: RequestBuilder) -> Option<String> {
+    match request.send() {
+        Ok(response) => {
+            if !response.status().is_success() {
+                eprintln!("{name} request returned non-success status: {}!", response.status());
+                return None;
+            }
+            match response.text() {
+                Ok(text) => Some(text),
+                Err(err) => {
+                    eprintln!("{name} response read failed: {err}!");
+                    None
+                }
+            }
+        }
+        Err(err) => {
+            eprintln!("{name} request failed: {err}!");
+            None
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use reqwest::blocking::Client;
+
+use super::{get_text, MarketProvider};
+use crate::markets::model::{FxPair, Quote};
+
+/// Stooq's CSV quote endpoint. No key; covers indices (`^spx`), exchange-suffixed stocks and ETFs
+/// (`spy.us`, `vwce.de`) and FX (`eurusd`), delayed 15 minutes or so.
+pub struct Stooq;
+
+// Fields we ask for with `f=`, in order: symbol, date, time, close, previous close, name.
+// The name goes last because it's the only one that can contain commas.
+const FIELDS: &str = "sd2t2cpn";
+
+struct Row {
+    symbol: String, // uppercase, as Stooq echoes it
+    date: Option<String>,
+    close: f64,
+    previous: Option<f64>,
+    name: String,
+}
+
+/// Parse the header-less CSV. Unknown symbols come back as a row of `N/D`s and are skipped.
+fn parse_csv(body: &str) -> Vec<Row> {
+    body.lines()
+        .filter_map(|line| {
+            let mut fields = line.trim().splitn(6, ',');
+            let symbol = fields.next()?.to_string();
+            let date = fields.next().filter(|d| *d != "N/D").map(str::to_string);
+            let _time = fields.next();
+            let close = fields.next()?.parse().ok()?;
+            let previous = fields.next().and_then(|p| p.parse().ok());
+            let name = fields.next().unwrap_or("").trim_matches('"').to_string();
+            Some(Row {
+                symbol,
+                date,
+                close,
+                previous,
+                name,
+            })
+        })
+        .collect()
+}
+
+impl Stooq {
+    fn fetch(&self, client: &Client, symbols: &[String]) -> Option<Vec<Row>> {
+        // Symbols are separated by '+', which `query()` produces from spaces
+        let request = client.get("https://stooq.com/q/l/").query(&[
+            ("s", symbols.join(" ").as_str()),
+            ("f", FIELDS),
+            ("e", "csv"),
+        ]);
+        Some(parse_csv(&get_text("Stooq", request)?))
+    }
+}
+
+impl MarketProvider for Stooq {
+    fn name(&self) -> &'static str {
+        "stooq"
+    }
+
+    fn quotes(&self, client: &Client, symbols: &[String]) -> Option<Vec<Quote>> {
+        let rows = self.fetch(client, symbols)?;
+        Some(
+            symbols
+                .iter()
+                .filter_map(|symbol| {
+                    let row = rows.iter().find(|r| r.symbol.eq_ignore_ascii_case(symbol))?;
+                    Some(Quote {
+                        symbol: symbol.clone(),
+                        name: if row.name.is_empty() {
+                            symbol.to_uppercase()
+                        } else {
+                            row.name.clone()
+                        },
+                        price: row.close,
+                        previous: row.previous,
+                        date: row.date.clone(),
+                        provider: self.name().to_string(),
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    fn fx(&self, client: &Client, pairs: &[FxPair]) -> Option<Vec<Quote>> {
+        let symbols: Vec<String> = pairs
+            .iter()
+            .map(|p| format!("{}{}", p.base, p.quote).to_lowercase())
+            .collect();
+        let rows = self.fetch(client, &symbols)?;
+        Some(
+            pairs
+                .iter()
+                .zip(&symbols)
+                .filter_map(|(pair, symbol)| {
+                    let row = rows.iter().find(|r| r.symbol.eq_ignore_ascii_case(symbol))?;
+                    Some(Quote {
+                        symbol: pair.symbol(),
+                        name: pair.symbol(),
+                        price: row.close,
+                        previous: row.previous,
+                        date: row.date.clone(),
+                        provider: self.name().to_string(),
+                    })
+                })
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,61 @@
+use core::time::Duration;
+use reqwest::blocking::Client;
+use serde_json::json;
+
+use crate::markets::options::MarketsOptions;
+use crate::markets::providers::providers_from_env;
+
+pub fn query() -> Option<String> {
+    let client = match Client::builder()
+        .timeout(Duration::from_secs(10))
+        .user_agent("waybap/0.1.0")
+        .build()
+    {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Failed to build HTTP client for markets: {e}");
+            return None;
+        }
+    };
+    let options = MarketsOptions::from_env();
+    let providers = providers_from_env();
+
+    // Equities and FX fail over independently: the first provider with any results wins each.
+    // A section that nobody could serve is stored as null, so the tooltip can say so.
+    let quotes = if options.symbols.is_empty() {
+        Some(Vec::new())
+    } else {
+        providers.iter().find_map(|provider| {
+            let quotes = provider.quotes(&client, &options.symbols).filter(|q| !q.is_empty());
+            if quotes.is_none() {
+                eprintln!(
+                    "Markets provider '{}' has no quotes, trying the next one",
+                    provider.name()
+                );
+            }
+            quotes
+        })
+    };
+    let fx = if options.fx.is_empty() {
+        Some(Vec::new())
+    } else {
+        providers.iter().find_map(|provider| {
+            let fx = provider.fx(&client, &options.fx).filter(|q| !q.is_empty());
+            if fx.is_none() {
+                eprintln!(
+                    "Markets provider '{}' has no FX rates, trying the next one",
+                    provider.name()
+                );
+            }
+            fx
+        })
+    };
+
+    // Nothing configured came back: keep the previous cache rather than overwrite it with nothing
+    let has_quotes = quotes.as_ref().is_some_and(|q| !q.is_empty());
+    let has_fx = fx.as_ref().is_some_and(|f| !f.is_empty());
+    if !has_quotes && !has_fx {
+        return None;
+    }
+    Some(json!({ "quotes": quotes, "fx": fx }).to_string())
+}
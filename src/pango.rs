@@ -28,3 +28,41 @@ pub fn meter_bar(used_percent: f64, width: usize, filled_color: &str, empty_colo
         "░".repeat(empty),
     )
 }
+
+/// `values` averaged down to exactly `width` points when there are more than that, oldest first.
+/// Each point covers an equal share of the series (to within one value), so none is left over.
+pub fn downsample(values: &[f64], width: usize) -> Vec<f64> {
+    let n = values.len();
+    if n <= width {
+        return values.to_vec();
+    }
+    (0..width)
+        .map(|i| {
+            let bucket = &values[i * n / width..(i + 1) * n / width];
+            bucket.iter().sum::<f64>() / bucket.len() as f64
+        })
+        .collect()
+}
+
+/// `values` as block characters (▁ to █), averaged into at most `width` points by [`downsample`]
+/// and padded to `width` columns. `color` picks the line's color from the points (empty for an
+/// empty series).
+pub fn sparkline(values: &[f64], width: usize, color: impl Fn(&[f64]) -> &'static str) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    if width == 0 {
+        return String::new();
+    }
+    let points = downsample(values, width);
+    let min = points.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = points.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let line: String = points
+        .iter()
+        .map(|p| {
+            // A flat line sits in the middle rather than on the floor
+            let level = if max > min { (p - min) / (max - min) * 7.0 } else { 3.0 };
+            BLOCKS[level.round() as usize]
+        })
+        .collect();
+    let color = color(&points);
+    format!("<span foreground=\"{color}\">{line: <width$}</span>")
+}
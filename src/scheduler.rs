@@ -53,6 +53,21 @@ impl Job {
         }
     }
 
+    /// Run on the first tick unless `is_current` accepts the cached output. A cache written in an
+    /// older format would otherwise be served, and fail to parse, until the interval has passed.
+    pub fn stale_unless(mut self, is_current: fn(&str) -> bool) -> Job {
+        let cache_fp = get_cache_fp(&self.name);
+        if !fs::read_to_string(&cache_fp).is_ok_and(|raw| is_current(&raw)) {
+            println!(
+                "[{:?}]: {name} cache is missing or outdated",
+                chrono::Utc::now(),
+                name = self.name
+            );
+            self.last_run = Utc.timestamp_opt(1, 0).unwrap();
+        }
+        self
+    }
+
     fn tick(&mut self) {
         let now = Utc::now();
 
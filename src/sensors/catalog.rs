@@ -0,0 +1,189 @@
+use super::options::Thresholds;
+
+/// Device category, which picks the default temperature color thresholds.
+#[derive(Clone, Copy)]
+pub enum SensorKind {
+    CpuGpu,      // CPU/GPU: 50/70/85
+    Nvme,        // NVMe drives throttle at ~70°C
+    Ram,         // DDR5: normal 30-50, concerning at 50+
+    Motherboard, // Mixed sensors, generous thresholds
+}
+
+impl SensorKind {
+    /// Fallback bands when neither the chip nor the config gives limits.
+    pub fn thresholds(self) -> Thresholds {
+        let (warm, hot, crit) = match self {
+            SensorKind::CpuGpu => (50.0, 70.0, 85.0),
+            SensorKind::Nvme => (40.0, 55.0, 70.0),
+            SensorKind::Ram => (40.0, 50.0, 60.0),
+            SensorKind::Motherboard => (50.0, 70.0, 85.0),
+        };
+        Thresholds { warm, hot, crit }
+    }
+
+    fn parse(s: &str) -> Option<SensorKind> {
+        match s.trim().to_lowercase().as_str() {
+            "cpu" | "gpu" => Some(SensorKind::CpuGpu),
+            "nvme" | "drive" => Some(SensorKind::Nvme),
+            "ram" => Some(SensorKind::Ram),
+            "motherboard" => Some(SensorKind::Motherboard),
+            _ => None,
+        }
+    }
+}
+
+/// A tooltip section for hwmon chips matching `pattern`: the exact name, or a prefix with a
+/// trailing `*` ("nvme*").
+pub struct ChipEntry {
+    pub pattern: String,
+    pub icon: String,
+    pub title: String,
+    pub kind: SensorKind,
+}
+
+impl ChipEntry {
+    /// How specifically this entry matches `chip`, if at all: exact beats any prefix, longer
+    /// prefixes beat shorter ones.
+    fn specificity(&self, chip: &str) -> Option<usize> {
+        match self.pattern.strip_suffix('*') {
+            Some(prefix) => chip.starts_with(prefix).then_some(prefix.len()),
+            None => (chip == self.pattern).then_some(usize::MAX),
+        }
+    }
+
+    pub fn header(&self) -> String {
+        if self.icon.is_empty() {
+            self.title.clone()
+        } else {
+            format!("{} {}", self.icon, self.title)
+        }
+    }
+}
+
+// Built-in catalog in display order: (pattern, nerd font icon, title, kind)
+// Icons: 󰻠 cpu(F0EE0), 󰢮 expansion_card(F08AE), 󰋊 harddisk(F02CA), 󰘚 chip(F061A), 󰍛 memory(F035B),
+//        󰌢 laptop(F0322), 󰖩 wifi(F05A9), 󰌘 lan(F0318), 󰈐 fan(F0210), 󰚥 power_plug(F06A5),
+//        󰁹 battery(F0079), 󰔏 thermometer(F050F)
+const BUILTIN: &[(&str, &str, &str, SensorKind)] = &[
+    ("k10temp", "\u{F0EE0}", "CPU", SensorKind::CpuGpu),
+    ("coretemp", "\u{F0EE0}", "CPU", SensorKind::CpuGpu),
+    ("zenpower", "\u{F0EE0}", "CPU", SensorKind::CpuGpu),
+    ("cpu_thermal", "\u{F0EE0}", "CPU", SensorKind::CpuGpu), // Raspberry Pi and other SoCs
+    ("amdgpu", "\u{F08AE}", "GPU AMD", SensorKind::CpuGpu),
+    ("radeon", "\u{F08AE}", "GPU AMD", SensorKind::CpuGpu),
+    ("i915", "\u{F08AE}", "GPU Intel", SensorKind::CpuGpu),
+    ("xe", "\u{F08AE}", "GPU Intel", SensorKind::CpuGpu),
+    ("nouveau", "\u{F08AE}", "GPU NVIDIA", SensorKind::CpuGpu),
+    ("nvme*", "\u{F02CA}", "NVMe", SensorKind::Nvme),
+    ("drivetemp", "\u{F02CA}", "Drives", SensorKind::Nvme), // SATA/SAS disks
+    ("nct*", "\u{F061A}", "Motherboard", SensorKind::Motherboard), // Nuvoton Super I/O
+    ("it8*", "\u{F061A}", "Motherboard", SensorKind::Motherboard), // ITE Super I/O
+    ("f71*", "\u{F061A}", "Motherboard", SensorKind::Motherboard), // Fintek Super I/O
+    ("w83*", "\u{F061A}", "Motherboard", SensorKind::Motherboard), // Winbond Super I/O
+    ("asusec", "\u{F061A}", "Motherboard EC", SensorKind::Motherboard),
+    (
+        "asus_wmi_sensors",
+        "\u{F061A}",
+        "Motherboard EC",
+        SensorKind::Motherboard,
+    ),
+    ("gigabyte_wmi", "\u{F061A}", "Motherboard EC", SensorKind::Motherboard),
+    ("thinkpad", "\u{F0322}", "Laptop", SensorKind::Motherboard),
+    ("dell_smm", "\u{F0322}", "Laptop", SensorKind::Motherboard),
+    ("applesmc", "\u{F0322}", "Laptop", SensorKind::Motherboard),
+    ("asus", "\u{F0322}", "Laptop", SensorKind::Motherboard),
+    ("spd5118", "\u{F035B}", "RAM", SensorKind::Ram), // DDR5
+    ("jc42", "\u{F035B}", "RAM", SensorKind::Ram),    // DDR4
+    ("iwlwifi*", "\u{F05A9}", "Wi-Fi", SensorKind::Motherboard),
+    ("mt7*", "\u{F05A9}", "Wi-Fi", SensorKind::Motherboard),
+    ("ath1*", "\u{F05A9}", "Wi-Fi", SensorKind::Motherboard),
+    ("r8169*", "\u{F0318}", "Ethernet", SensorKind::Motherboard),
+    ("atlantic", "\u{F0318}", "Ethernet", SensorKind::Motherboard),
+    ("nzxt*", "\u{F0210}", "Cooling", SensorKind::Motherboard),
+    ("corsaircpro", "\u{F0210}", "Cooling", SensorKind::Motherboard),
+    ("corsairpsu", "\u{F06A5}", "Power Supply", SensorKind::Motherboard),
+    ("BAT*", "\u{F0079}", "Battery", SensorKind::Motherboard),
+    ("acpitz", "\u{F050F}", "ACPI", SensorKind::Motherboard),
+];
+
+/// Known chips in display order. Unknown chips render after them under their raw name.
+pub struct Catalog {
+    pub entries: Vec<ChipEntry>,
+}
+
+impl Catalog {
+    pub fn from_env() -> Catalog {
+        let mut entries: Vec<ChipEntry> = BUILTIN
+            .iter()
+            .map(|&(pattern, icon, title, kind)| ChipEntry {
+                pattern: pattern.to_string(),
+                icon: icon.to_string(),
+                title: title.to_string(),
+                kind,
+            })
+            .collect();
+
+        // WAYBAP_SENSORS_CHIPS: pattern=title[|icon[|kind]],... A built-in pattern is replaced in
+        // place (keeping its icon and kind unless given), anything else is added at the end.
+        for item in crate::config::env_list("WAYBAP_SENSORS_CHIPS").unwrap_or_default() {
+            let Some((pattern, rest)) = item.split_once('=') else {
+                eprintln!("WAYBAP_SENSORS_CHIPS: invalid entry '{item}' (expected pattern=title[|icon[|kind]])");
+                continue;
+            };
+            let pattern = pattern.trim();
+            let mut fields = rest.splitn(3, '|').map(str::trim);
+            let title = fields.next().unwrap_or_default().to_string();
+            let icon = fields.next().map(str::to_string);
+            let kind = match fields.next() {
+                Some(k) => {
+                    match SensorKind::parse(k) {
+                        Some(kind) => Some(kind),
+                        None => {
+                            eprintln!("WAYBAP_SENSORS_CHIPS: unknown kind '{k}' (known: cpu, gpu, nvme, drive, ram, motherboard)");
+                            None
+                        }
+                    }
+                }
+                None => None,
+            };
+            match entries.iter_mut().find(|e| e.pattern == pattern) {
+                Some(entry) => {
+                    entry.title = title;
+                    if let Some(icon) = icon {
+                        entry.icon = icon;
+                    }
+                    entry.kind = kind.unwrap_or(entry.kind);
+                }
+                None => entries.push(ChipEntry {
+                    pattern: pattern.to_string(),
+                    icon: icon.unwrap_or_default(),
+                    title,
+                    kind: kind.unwrap_or(SensorKind::Motherboard),
+                }),
+            }
+        }
+
+        // WAYBAP_SENSORS_ORDER: titles to show first, in this order; the rest keep catalog order
+        let order: Vec<String> = crate::config::env_list("WAYBAP_SENSORS_ORDER")
+            .unwrap_or_default()
+            .iter()
+            .map(|t| t.to_lowercase())
+            .collect();
+        entries.sort_by_key(|e| {
+            let title = e.title.to_lowercase();
+            order.iter().position(|t| *t == title).unwrap_or(order.len())
+        });
+
+        Catalog { entries }
+    }
+
+    /// Index of the most specific entry for a hwmon chip name.
+    pub fn lookup(&self, chip: &str) -> Option<usize> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, e)| e.specificity(chip).map(|s| (s, i)))
+            .max_by_key(|&(s, i)| (s, std::cmp::Reverse(i)))
+            .map(|(_, i)| i)
+    }
+}
@@ -0,0 +1,229 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::query::read_number;
+use super::Gpu;
+
+// nvidia-smi is expensive (~100ms per call), so we cache its result and only
+// re-query every NVIDIA_INTERVAL seconds. The hwmon sysfs reads are virtually
+// free (kernel virtual filesystem) and run every time.
+const NVIDIA_INTERVAL: Duration = Duration::from_secs(10);
+
+static NVIDIA_CACHE: Mutex<(Vec<Gpu>, Option<Instant>)> = Mutex::new((Vec::new(), None));
+
+// Fields asked of nvidia-smi, in the order `parse_nvidia` expects them
+const NVIDIA_FIELDS: &str = "index,name,pci.bus_id,temperature.gpu,fan.speed,utilization.gpu,memory.used,memory.total,power.draw,power.limit,clocks.gr,clocks.mem";
+
+/// Parse `nvidia-smi --query-gpu=NVIDIA_FIELDS --format=csv,noheader,nounits` output, one GPU per
+/// line. Unsupported fields read "[N/A]" or "[Not Supported]" and become `None`.
+pub(super) fn parse_nvidia(stdout: &str) -> Vec<Gpu> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let [index, name, bus_id, temp, fan, utilization, memory_used, memory_total, power_draw, power_limit, clock_graphics, clock_memory] =
+                fields[..]
+            else {
+                return None;
+            };
+            let number = |s: &str| s.parse::<f64>().ok();
+            // nvidia-smi pads the PCI domain to 8 digits ("00000000:01:00.0"), sysfs uses 4
+            let bus_id = match bus_id.split_once(':') {
+                Some((domain, rest)) if domain.len() > 4 => format!("{}:{rest}", &domain[domain.len() - 4..]),
+                _ => bus_id.to_string(),
+            };
+            Some(Gpu {
+                driver: "nvidia".to_string(),
+                index: index.parse().ok()?,
+                name: name.to_string(),
+                bus_id: bus_id.to_lowercase(),
+                temp: number(temp),
+                fan: number(fan),
+                utilization: number(utilization),
+                memory_used: number(memory_used),
+                memory_total: number(memory_total),
+                power_draw: number(power_draw),
+                power_limit: number(power_limit),
+                clock_graphics: number(clock_graphics),
+                clock_memory: number(clock_memory),
+                rc6: None,
+                rc6_residency_ms: None,
+            })
+        })
+        .collect()
+}
+
+/// Run `nvidia_smi` (the `nvidia-smi` command, with whatever environment the caller gave it) and
+/// parse its output. Empty if it's missing, fails or takes longer than 5 seconds.
+pub(super) fn run_nvidia_smi(mut nvidia_smi: Command) -> Vec<Gpu> {
+    // Run nvidia-smi in a thread with a 5-second timeout to avoid blocking
+    // the scheduler forever if nvidia-smi hangs (driver bug, GPU lockup).
+    let (tx, rx) = std::sync::mpsc::channel::<Vec<Gpu>>();
+    std::thread::spawn(move || {
+        let result = nvidia_smi
+            .args([&format!("--query-gpu={NVIDIA_FIELDS}"), "--format=csv,noheader,nounits"])
+            .output()
+            .ok()
+            .and_then(|out| {
+                if out.status.success() {
+                    let stdout = String::from_utf8(out.stdout).ok()?;
+                    Some(parse_nvidia(&stdout))
+                } else {
+                    None
+                }
+            })
+            .unwrap_or_default();
+        let _ = tx.send(result);
+    });
+
+    rx.recv_timeout(Duration::from_secs(5)).unwrap_or_default()
+}
+
+fn query_nvidia() -> Vec<Gpu> {
+    let mut cache = NVIDIA_CACHE.lock().unwrap();
+    let stale = cache.1.map(|t| t.elapsed() >= NVIDIA_INTERVAL).unwrap_or(true);
+    if !stale {
+        return cache.0.clone();
+    }
+
+    let gpus = run_nvidia_smi(Command::new("nvidia-smi"));
+    *cache = (gpus.clone(), Some(Instant::now()));
+    gpus
+}
+
+/// Percent of the time between `prev` (residency in ms, when it was read) and `now` that the GPU
+/// spent in RC6 (idle).
+pub(super) fn rc6_percent(prev: (f64, Instant), residency_ms: f64, now: Instant) -> Option<f64> {
+    let (prev_ms, at) = prev;
+    let elapsed_ms = now.checked_duration_since(at)?.as_secs_f64() * 1000.0;
+    (elapsed_ms > 0.0).then(|| ((residency_ms - prev_ms) / elapsed_ms * 100.0).clamp(0.0, 100.0))
+}
+
+// RC6 residency is a running total, so a percentage needs the previous reading: (bus id, ms, when)
+static RC6_PREV: Mutex<Vec<(String, f64, Instant)>> = Mutex::new(Vec::new());
+
+/// Turn each GPU's RC6 total into `rc6`, against the reading the previous call kept. The first
+/// call for a card has nothing to compare with and leaves it `None`.
+pub(super) fn record_rc6(gpus: &mut [Gpu]) {
+    let mut prev = RC6_PREV.lock().unwrap();
+    let now = Instant::now();
+    for gpu in gpus {
+        let Some(residency_ms) = gpu.rc6_residency_ms else {
+            continue;
+        };
+        let last = prev.iter().position(|(id, ..)| *id == gpu.bus_id);
+        gpu.rc6 = last.and_then(|i| rc6_percent((prev[i].1, prev[i].2), residency_ms, now));
+        let reading = (gpu.bus_id.clone(), residency_ms, now);
+        match last {
+            Some(i) => prev[i] = reading,
+            None => prev.push(reading),
+        }
+    }
+}
+
+/// The current clock from an amdgpu `pp_dpm_sclk`/`pp_dpm_mclk` table: the level marked with `*`,
+/// like "1: 2100Mhz *".
+fn dpm_clock(path: &Path) -> Option<f64> {
+    let table = fs::read_to_string(path).ok()?;
+    let level = table.lines().find(|l| l.trim_end().ends_with('*'))?;
+    let clock = level.split_whitespace().nth(1)?.to_lowercase();
+    clock.strip_suffix("mhz")?.parse().ok()
+}
+
+/// The hwmon directory a DRM device registers, for its temperature and power.
+fn device_hwmon(device: &Path) -> Option<PathBuf> {
+    fs::read_dir(device.join("hwmon"))
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .next()
+}
+
+/// AMD and Intel GPUs from `class/drm/card*` under the sysfs `root`. NVIDIA cards are left to
+/// nvidia-smi.
+fn query_drm(root: &Path) -> Vec<Gpu> {
+    let Ok(entries) = fs::read_dir(root.join("class/drm")) else {
+        return Vec::new();
+    };
+    let file_name = |p: &Path| p.file_name().map(|n| n.to_string_lossy().into_owned());
+
+    let mut gpus = Vec::new();
+    for entry in entries.flatten() {
+        // card0, card1; not connectors (card0-DP-1) or render nodes (renderD128)
+        let card_name = entry.file_name().to_string_lossy().into_owned();
+        let Some(index) = card_name.strip_prefix("card").and_then(|n| n.parse().ok()) else {
+            continue;
+        };
+        let card = entry.path();
+        let device = card.join("device");
+        // The driver link's target needn't exist, as for `subsystem` in query.rs
+        let driver = fs::read_link(device.join("driver")).ok().and_then(|p| file_name(&p));
+        let bus_id = fs::canonicalize(&device).ok().and_then(|p| file_name(&p));
+        let (Some(driver), Some(bus_id)) = (driver, bus_id) else {
+            continue;
+        };
+        let hwmon = device_hwmon(&device);
+        let hwmon_value = |file: &str, scale: f64| read_number(&hwmon.as_ref()?.join(file)).map(|v| v / scale);
+        let mib = |file: &str| read_number(&device.join(file)).map(|bytes| bytes / 1_048_576.0);
+        let product = fs::read_to_string(device.join("product_name"))
+            .ok()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty());
+
+        let mut gpu = Gpu {
+            index,
+            name: String::new(),
+            bus_id,
+            temp: hwmon_value("temp1_input", 1000.0),
+            fan: None, // amdgpu reports RPM, which the hwmon section already shows
+            utilization: None,
+            memory_used: None,
+            memory_total: None,
+            power_draw: hwmon_value("power1_average", 1_000_000.0).or_else(|| hwmon_value("power1_input", 1_000_000.0)),
+            power_limit: hwmon_value("power1_cap", 1_000_000.0),
+            clock_graphics: None,
+            clock_memory: None,
+            rc6: None,
+            rc6_residency_ms: None,
+            driver,
+        };
+        match gpu.driver.as_str() {
+            "amdgpu" => {
+                gpu.name = product.unwrap_or_else(|| "AMD GPU".to_string());
+                gpu.utilization = read_number(&device.join("gpu_busy_percent"));
+                gpu.memory_used = mib("mem_info_vram_used");
+                gpu.memory_total = mib("mem_info_vram_total");
+                gpu.clock_graphics = dpm_clock(&device.join("pp_dpm_sclk"));
+                gpu.clock_memory = dpm_clock(&device.join("pp_dpm_mclk"));
+            }
+            "i915" => {
+                gpu.name = product.unwrap_or_else(|| "Intel GPU".to_string());
+                gpu.clock_graphics =
+                    read_number(&card.join("gt_act_freq_mhz")).or_else(|| read_number(&card.join("gt_cur_freq_mhz")));
+                gpu.rc6_residency_ms = read_number(&card.join("power/rc6_residency_ms"));
+            }
+            "xe" => {
+                // The primary GT; media GTs (gt1) have their own clocks and idle counters
+                let gt = device.join("tile0/gt0");
+                gpu.name = product.unwrap_or_else(|| "Intel GPU".to_string());
+                gpu.clock_graphics =
+                    read_number(&gt.join("freq0/act_freq")).or_else(|| read_number(&gt.join("freq0/cur_freq")));
+                gpu.rc6_residency_ms = read_number(&gt.join("gtidle/idle_residency_ms"));
+            }
+            _ => continue,
+        }
+        gpus.push(gpu);
+    }
+    gpus.sort_by_key(|g| g.index);
+    gpus
+}
+
+/// All GPUs: AMD and Intel from DRM sysfs (cheap, every call), then NVIDIA from nvidia-smi.
+pub fn query(root: &Path) -> Vec<Gpu> {
+    let mut gpus = query_drm(root);
+    gpus.extend(query_nvidia());
+    gpus
+}
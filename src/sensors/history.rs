@@ -0,0 +1,101 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use crate::config::env_parse;
+
+use super::{History, SensorData};
+
+const DEFAULT_HISTORY_SECS: usize = 600;
+const DEFAULT_SPARKLINE_WIDTH: usize = 12;
+
+// The trend compares the mean of the last TREND_SAMPLES samples with the mean of the
+// TREND_SAMPLES ending TREND_SPAN samples earlier, so a single noisy reading doesn't flip it
+const TREND_SAMPLES: usize = 5;
+const TREND_SPAN: usize = 30;
+
+/// One temperature's samples over the window (one per job run, so one per second), and its
+/// extremes and running total since waybap started.
+struct Series {
+    window: VecDeque<f64>,
+    min: f64,
+    max: f64,
+    sum: f64,
+    count: u64,
+}
+
+impl Series {
+    fn push(&mut self, temp: f64, capacity: usize) {
+        while self.window.len() >= capacity {
+            self.window.pop_front();
+        }
+        self.window.push_back(temp);
+        self.min = self.min.min(temp);
+        self.max = self.max.max(temp);
+        self.sum += temp;
+        self.count += 1;
+    }
+
+    fn mean(samples: impl Iterator<Item = f64>) -> f64 {
+        let (sum, n) = samples.fold((0.0, 0), |(sum, n), v| (sum + v, n + 1));
+        sum / n as f64
+    }
+
+    fn trend(&self) -> Option<f64> {
+        let len = self.window.len();
+        if len < TREND_SPAN + TREND_SAMPLES {
+            return None;
+        }
+        let recent = Self::mean(self.window.range(len - TREND_SAMPLES..).copied());
+        let before = Self::mean(
+            self.window
+                .range(len - TREND_SPAN - TREND_SAMPLES..len - TREND_SPAN)
+                .copied(),
+        );
+        Some(recent - before)
+    }
+}
+
+// Series by reading: "{device id or chip name}#{n}:{label}", `n` telling apart devices that share
+// a name and have no id (two NVMe drives without serials)
+static HISTORY: Mutex<Option<HashMap<String, Series>>> = Mutex::new(None);
+
+/// Add this query's temperatures to their series and attach each one's history, once it has more
+/// than one sample. WAYBAP_SENSORS_HISTORY_SECS=0 turns history off.
+pub fn record(data: &mut SensorData) {
+    let capacity = env_parse("WAYBAP_SENSORS_HISTORY_SECS", DEFAULT_HISTORY_SECS);
+    if capacity == 0 {
+        return;
+    }
+    let width = env_parse("WAYBAP_SENSORS_SPARKLINE_WIDTH", DEFAULT_SPARKLINE_WIDTH);
+
+    let mut history = HISTORY.lock().unwrap();
+    let history = history.get_or_insert_with(HashMap::new);
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for group in &mut data.sensors {
+        let device = group.id.clone().unwrap_or_else(|| group.name.clone());
+        let n = seen.entry(device.clone()).or_default();
+        *n += 1;
+        for reading in &mut group.readings {
+            let series = history
+                .entry(format!("{device}#{n}:{}", reading.label))
+                .or_insert_with(|| Series {
+                    window: VecDeque::with_capacity(capacity),
+                    min: f64::INFINITY,
+                    max: f64::NEG_INFINITY,
+                    sum: 0.0,
+                    count: 0,
+                });
+            series.push(reading.temp, capacity);
+            if series.count < 2 {
+                continue;
+            }
+            reading.history = Some(History {
+                min: series.min,
+                max: series.max,
+                avg: series.sum / series.count as f64,
+                samples: crate::pango::downsample(series.window.make_contiguous(), width),
+                trend: series.trend(),
+            });
+        }
+    }
+}
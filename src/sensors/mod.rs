@@ -1,8 +1,16 @@
+mod catalog;
+mod gpu;
+mod history;
+mod options;
 mod parsing;
 mod query;
+mod snapshot;
+#[cfg(test)]
+mod tests;
 
 pub use parsing::parse_data;
 pub use query::query;
+pub use snapshot::snapshot;
 
 use serde::{Deserialize, Serialize};
 
@@ -10,16 +18,84 @@ use serde::{Deserialize, Serialize};
 pub(crate) struct SensorReading {
     pub label: String,
     pub temp: f64,
+    // Limits the chip reports for this sensor (`temp*_max`, `temp*_crit`, `temp*_emergency`), °C
+    #[serde(default)]
+    pub max: Option<f64>,
+    #[serde(default)]
+    pub crit: Option<f64>,
+    #[serde(default)]
+    pub emergency: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history: Option<History>,
+}
+
+/// How a temperature has moved since waybap started, from the history the sensors job keeps.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct History {
+    pub min: f64,
+    pub max: f64,
+    pub avg: f64,
+    pub samples: Vec<f64>,  // the last WAYBAP_SENSORS_HISTORY_SECS, averaged down for the sparkline
+    pub trend: Option<f64>, // change over the last 30 s, °C; `None` until there are enough samples
+}
+
+/// A non-temperature hwmon reading, already in display units (RPM, V, W, A), with the chip's
+/// limits when it reports them.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct Measurement {
+    pub label: String,
+    pub value: f64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub crit: Option<f64>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub(crate) struct SensorGroup {
     pub name: String,
-    pub readings: Vec<SensorReading>,
+    // Stable id of the parent device ("nvme:S6Z1NJ0W123456", "pci:0000:03:00.0"), for aliases
+    #[serde(default)]
+    pub id: Option<String>,
+    // Model the kernel reports for the parent device, like "Samsung SSD 990 PRO 2TB (nvme0)"
+    #[serde(default)]
+    pub device: Option<String>,
+    pub readings: Vec<SensorReading>, // temperatures
+    #[serde(default)]
+    pub fans: Vec<Measurement>,
+    #[serde(default)]
+    pub voltages: Vec<Measurement>,
+    #[serde(default)]
+    pub power: Vec<Measurement>,
+    #[serde(default)]
+    pub currents: Vec<Measurement>,
+}
+
+/// One GPU, from nvidia-smi or DRM sysfs (amdgpu, i915, xe). Fields the driver doesn't report
+/// ("[N/A]" from nvidia-smi, VRAM on Intel) are `None`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct Gpu {
+    pub driver: String, // "nvidia", "amdgpu", "i915" or "xe"
+    pub index: u32,     // nvidia-smi index, or the DRM card number
+    pub name: String,
+    pub bus_id: String,              // PCI address in sysfs form ("0000:01:00.0")
+    pub temp: Option<f64>,           // °C
+    pub fan: Option<f64>,            // %
+    pub utilization: Option<f64>,    // %
+    pub memory_used: Option<f64>,    // MiB
+    pub memory_total: Option<f64>,   // MiB
+    pub power_draw: Option<f64>,     // W
+    pub power_limit: Option<f64>,    // W
+    pub clock_graphics: Option<f64>, // MHz
+    pub clock_memory: Option<f64>,   // MHz
+    pub rc6: Option<f64>,            // % of the time since the last query spent idle in RC6 (Intel)
+    // The running RC6 total `rc6` is computed from (ms), see `gpu::record_rc6`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rc6_residency_ms: Option<f64>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub(crate) struct SensorData {
     pub sensors: Vec<SensorGroup>,
-    pub nvidia: Vec<f64>,
+    #[serde(default)]
+    pub gpus: Vec<Gpu>,
 }
@@ -0,0 +1,160 @@
+use std::path::PathBuf;
+
+use super::catalog::Catalog;
+
+/// Where sysfs is mounted: WAYBAP_SENSORS_SYSFS_ROOT, to read a snapshot from
+/// `waybap sensors snapshot` instead of this machine.
+pub fn sysfs_root() -> PathBuf {
+    std::env::var("WAYBAP_SENSORS_SYSFS_ROOT")
+        .ok()
+        .filter(|root| !root.trim().is_empty())
+        .map_or_else(|| PathBuf::from("/sys"), PathBuf::from)
+}
+
+/// Picks readings: a chip name (prefix match, like `nvme`) and optionally a label (case-insensitive,
+/// a trailing `*` makes it a prefix, like `AUXTIN*`).
+pub struct Selector {
+    pub chip: Option<String>,
+    pub label: Option<String>,
+}
+
+impl Selector {
+    /// "chip[:label]"; either part may be empty.
+    fn parse(s: &str) -> Selector {
+        let (chip, label) = match s.split_once(':') {
+            Some((chip, label)) => (chip, Some(label)),
+            None => (s, None),
+        };
+        Selector {
+            chip: Some(chip.trim().to_string()).filter(|c| !c.is_empty()),
+            label: label.map(|l| l.trim().to_string()).filter(|l| !l.is_empty()),
+        }
+    }
+
+    pub fn matches(&self, chip: &str, label: &str) -> bool {
+        self.chip.as_ref().is_none_or(|c| chip.starts_with(c.as_str()))
+            && self.label.as_ref().is_none_or(|l| match l.strip_suffix('*') {
+                Some(prefix) => label.to_lowercase().starts_with(&prefix.to_lowercase()),
+                None => l.eq_ignore_ascii_case(label),
+            })
+    }
+}
+
+/// `chip[:label]=value` entries of a list env var, logging the ones that don't parse.
+fn selector_list<T>(name: &str, expected: &str, value: impl Fn(&Selector, &str) -> Option<T>) -> Vec<(Selector, T)> {
+    crate::config::env_list(name)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|entry| {
+            let parsed = entry.split_once('=').and_then(|(target, v)| {
+                let selector = Selector::parse(target);
+                let v = value(&selector, v.trim())?;
+                Some((selector, v))
+            });
+            if parsed.is_none() {
+                eprintln!("{name}: invalid entry '{entry}' (expected {expected})");
+            }
+            parsed
+        })
+        .collect()
+}
+
+/// Temperature color bands in °C: yellow from `warm`, peach from `hot`, red from `crit`.
+#[derive(Clone, Copy)]
+pub struct Thresholds {
+    pub warm: f64,
+    pub hot: f64,
+    pub crit: f64,
+}
+
+impl Thresholds {
+    /// "warm/hot/crit", like "60/80/95".
+    fn parse(s: &str) -> Option<Thresholds> {
+        let values: Vec<f64> = s.split('/').map(|v| v.trim().parse().ok()).collect::<Option<_>>()?;
+        match values[..] {
+            [warm, hot, crit] if warm <= hot && hot <= crit => Some(Thresholds { warm, hot, crit }),
+            _ => None,
+        }
+    }
+}
+
+/// What the bar text shows.
+pub enum BarItem {
+    Temp,            // CPU temperature
+    Power(Selector), // W; default: the CPU package, else the first power reading
+    Fan(Selector),   // RPM; default: the first spinning fan
+}
+
+/// Sensors module configuration, read from WAYBAP_* env vars.
+pub struct SensorsOptions {
+    pub bar: BarItem,
+    pub catalog: Catalog,
+    pub limits: Vec<(Selector, Thresholds)>, // first match wins over the chip's own limits
+    pub show_limits: bool,
+    pub trend: bool, // rising/falling arrow after the CPU temperature in the bar
+    pub hide: Vec<Selector>,
+    pub renames: Vec<(Selector, String)>, // first match wins
+    pub aliases: Vec<(String, String)>,   // device id -> display name
+}
+
+impl SensorsOptions {
+    pub fn from_env() -> SensorsOptions {
+        // WAYBAP_SENSORS_BAR: temp | power[:chip[:label]] | fan[:chip[:label]]
+        let raw = std::env::var("WAYBAP_SENSORS_BAR").unwrap_or_default();
+        let mut parts = raw.trim().splitn(3, ':');
+        let kind = parts.next().unwrap_or("").to_lowercase();
+        let selector = Selector {
+            chip: parts.next().filter(|c| !c.is_empty()).map(str::to_string),
+            label: parts.next().filter(|l| !l.is_empty()).map(str::to_string),
+        };
+        let bar = match kind.as_str() {
+            "" | "temp" => BarItem::Temp,
+            "power" => BarItem::Power(selector),
+            "fan" => BarItem::Fan(selector),
+            other => {
+                eprintln!("WAYBAP_SENSORS_BAR: unknown item '{other}' (known: temp, power, fan)");
+                BarItem::Temp
+            }
+        };
+
+        // WAYBAP_SENSORS_HIDE: chip[:label],...
+        let hide = crate::config::env_list("WAYBAP_SENSORS_HIDE")
+            .unwrap_or_default()
+            .iter()
+            .map(|entry| Selector::parse(entry))
+            .collect();
+
+        // WAYBAP_SENSORS_ALIASES: device id=name,...
+        let aliases = crate::config::env_list("WAYBAP_SENSORS_ALIASES")
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|entry| {
+                let alias = entry
+                    .split_once('=')
+                    .map(|(id, name)| (id.trim().to_string(), name.trim().to_string()))
+                    .filter(|(id, name)| !id.is_empty() && !name.is_empty());
+                if alias.is_none() {
+                    eprintln!("WAYBAP_SENSORS_ALIASES: invalid entry '{entry}' (expected id=name)");
+                }
+                alias
+            })
+            .collect();
+
+        SensorsOptions {
+            bar,
+            catalog: Catalog::from_env(),
+            // WAYBAP_SENSORS_LIMITS: chip[:label]=warm/hot/crit,...
+            limits: selector_list("WAYBAP_SENSORS_LIMITS", "chip[:label]=warm/hot/crit", |_, v| {
+                Thresholds::parse(v)
+            }),
+            show_limits: crate::config::env_flag("WAYBAP_SENSORS_SHOW_LIMITS", false),
+            trend: crate::config::env_flag("WAYBAP_SENSORS_TREND", false),
+            hide,
+            // WAYBAP_SENSORS_RENAME: chip:label=new label,...
+            renames: selector_list("WAYBAP_SENSORS_RENAME", "chip:label=new label", |sel, v| {
+                (sel.label.is_some() && !v.is_empty()).then(|| v.to_string())
+            }),
+            aliases,
+        }
+    }
+}
@@ -1,132 +1,337 @@
 use serde_json::{json, value::from_value, Value};
 
-use super::SensorData;
+use super::catalog::SensorKind;
+use super::options::{BarItem, Selector, SensorsOptions, Thresholds};
+use super::{Gpu, History, Measurement, SensorData, SensorGroup, SensorReading};
 
-// Sensor category determines temperature color thresholds
-#[derive(Clone, Copy)]
-enum SensorKind {
-    CpuGpu,      // CPU/GPU: 50/70/85
-    Nvme,        // NVMe drives throttle at ~70°C
-    Ram,         // DDR5: normal 30-50, concerning at 50+
-    Motherboard, // Mixed sensors, generous thresholds
+// Smallest change over the history's trend span (°C) that gets an arrow in the bar
+const TREND_THRESHOLD: f64 = 1.0;
+
+/// Bands for one reading: a WAYBAP_SENSORS_LIMITS override, else the chip's own limits, else the
+/// kind's defaults. From chip limits, `max` is hot and `crit` (or `emergency`) critical, with warm
+/// 15° below hot; a chip reporting only one limit has it treated as critical.
+fn thresholds(options: &SensorsOptions, chip: &str, reading: &SensorReading, kind: SensorKind) -> Thresholds {
+    if let Some((_, t)) = options.limits.iter().find(|(sel, _)| sel.matches(chip, &reading.label)) {
+        return *t;
+    }
+    let from_crit = |crit: f64| Thresholds {
+        warm: crit - 25.0,
+        hot: crit - 10.0,
+        crit,
+    };
+    match (reading.max, reading.crit.or(reading.emergency)) {
+        (Some(max), Some(crit)) if max < crit => Thresholds {
+            warm: max - 15.0,
+            hot: max,
+            crit,
+        },
+        (_, Some(crit)) => from_crit(crit), // NVMe drives often report max == crit
+        (Some(max), None) => from_crit(max),
+        (None, None) => kind.thresholds(),
+    }
 }
 
 // Catppuccin Frappe palette for temperature color coding
-fn temp_color(temp: f64, kind: SensorKind) -> &'static str {
-    match kind {
-        SensorKind::CpuGpu => {
-            if temp >= 85.0 {
-                "#e78284" // Red - critical
-            } else if temp >= 70.0 {
-                "#ef9f76" // Peach - hot
-            } else if temp >= 50.0 {
-                "#e5c890" // Yellow - warm
-            } else {
-                "#a6d189" // Green - cool
-            }
-        }
-        SensorKind::Nvme => {
-            if temp >= 70.0 {
-                "#e78284" // Red - throttling
-            } else if temp >= 55.0 {
-                "#ef9f76" // Peach - hot
-            } else if temp >= 40.0 {
-                "#e5c890" // Yellow - warm
-            } else {
-                "#a6d189" // Green - cool
-            }
-        }
-        SensorKind::Ram => {
-            if temp >= 60.0 {
-                "#e78284" // Red - critical for DDR5
-            } else if temp >= 50.0 {
-                "#ef9f76" // Peach - hot
-            } else if temp >= 40.0 {
-                "#e5c890" // Yellow - warm
-            } else {
-                "#a6d189" // Green - cool
-            }
-        }
-        SensorKind::Motherboard => {
-            if temp >= 85.0 {
-                "#e78284" // Red - critical
-            } else if temp >= 70.0 {
-                "#ef9f76" // Peach - hot
-            } else if temp >= 50.0 {
-                "#e5c890" // Yellow - warm
-            } else {
-                "#a6d189" // Green - cool
-            }
-        }
+fn temp_color(temp: f64, t: Thresholds) -> &'static str {
+    if temp >= t.crit {
+        "#e78284" // Red - critical
+    } else if temp >= t.hot {
+        "#ef9f76" // Peach - hot
+    } else if temp >= t.warm {
+        "#e5c890" // Yellow - warm
+    } else {
+        "#a6d189" // Green - cool
     }
 }
 
-fn format_temp(temp: f64, kind: SensorKind) -> String {
-    let color = temp_color(temp, kind);
-    format!("<span foreground=\"{color}\">{temp:>5.1}°C</span>")
-}
-
-// Known hwmon sensor names -> (display title, nerd font icon, sensor kind, match mode)
-// Icons: 󰻠 cpu(F0EE0), 󰢮 expansion_card(F08AE), 󰋊 harddisk(F02CA), 󰘚 chip(F061A), 󰍛 memory(F035B)
-const KNOWN_SENSORS: &[(&str, &str, &str, SensorKind, bool)] = &[
-    ("k10temp", "\u{F0EE0} CPU", "k10temp", SensorKind::CpuGpu, false),
-    ("coretemp", "\u{F0EE0} CPU", "coretemp", SensorKind::CpuGpu, false),
-    ("amdgpu", "\u{F08AE} GPU AMD", "amdgpu", SensorKind::CpuGpu, false),
-    ("nvme", "\u{F02CA} NVMe", "nvme", SensorKind::Nvme, true), // prefix match
-    (
-        "nct6799",
-        "\u{F061A} Motherboard",
-        "nct6799",
-        SensorKind::Motherboard,
-        false,
-    ),
-    ("spd5118", "\u{F035B} RAM", "spd5118", SensorKind::Ram, false),
-];
-
-fn sensor_matches(hwmon_name: &str, pattern: &str, prefix: bool) -> bool {
-    if prefix {
-        hwmon_name.starts_with(pattern)
+fn format_temp(temp: f64, t: Thresholds, show_limit: bool) -> String {
+    let color = temp_color(temp, t);
+    let limit = if show_limit {
+        format!(" <span foreground=\"#949cbb\">/ {:.0}°</span>", t.crit)
     } else {
-        hwmon_name == pattern
+        String::new()
+    };
+    format!("<span foreground=\"{color}\">{temp:>5.1}°C</span>{limit}")
+}
+
+/// A temperature's recent samples as a sparkline in its current color, then its session minimum,
+/// maximum and average, muted.
+fn format_history(history: &History, color: &'static str) -> String {
+    let sparkline = match history.samples.len() {
+        0 => String::new(),
+        width => format!(" {}", crate::pango::sparkline(&history.samples, width, |_| color)),
+    };
+    format!(
+        "{sparkline} <span foreground=\"#949cbb\">↓{:>3.0} ↑{:>3.0} ⌀{:>3.0}</span>",
+        history.min, history.max, history.avg
+    )
+}
+
+/// The label to show for a reading, or `None` if a WAYBAP_SENSORS_HIDE rule drops it.
+fn display_label(options: &SensorsOptions, chip: &str, label: &str) -> Option<String> {
+    if options.hide.iter().any(|sel| sel.matches(chip, label)) {
+        return None;
     }
+    let renamed = options.renames.iter().find(|(sel, _)| sel.matches(chip, label));
+    Some(renamed.map_or(label, |(_, name)| name.as_str()).to_string())
 }
 
-fn is_known_sensor(hwmon_name: &str) -> bool {
-    KNOWN_SENSORS
-        .iter()
-        .any(|(_, _, pat, _, pfx)| sensor_matches(hwmon_name, pat, *pfx))
+/// A device's own name: its WAYBAP_SENSORS_ALIASES entry, else the model the kernel reports.
+fn device_name<'a>(options: &'a SensorsOptions, group: &'a SensorGroup) -> Option<&'a str> {
+    let alias = group
+        .id
+        .as_ref()
+        .and_then(|id| options.aliases.iter().find(|(alias_id, _)| alias_id == id));
+    alias.map(|(_, name)| name.as_str()).or(group.device.as_deref())
+}
+
+/// Color for a reading against the chip's own limits; `None` (default text color) without any.
+/// A fan under its minimum is stalled, so that's critical too.
+fn measurement_color(m: &Measurement, fan: bool) -> Option<&'static str> {
+    if m.crit.is_some_and(|crit| m.value >= crit) {
+        Some("#e78284") // Red - at critical limit
+    } else if m.max.is_some_and(|max| m.value >= max) {
+        Some("#ef9f76") // Peach - at max
+    } else if m.min.is_some_and(|min| m.value < min) {
+        Some(if fan { "#e78284" } else { "#ef9f76" })
+    } else if m.min.is_some() || m.max.is_some() || m.crit.is_some() {
+        Some("#a6d189") // Green - within limits
+    } else {
+        None
+    }
+}
+
+// Units of the `measurements()` kinds (fans, voltages, power, currents), in render order
+const MEASUREMENT_UNITS: &[&str] = &["RPM", "V", "W", "A"];
+
+fn measurements(group: &SensorGroup) -> [&[Measurement]; 4] {
+    [&group.fans, &group.voltages, &group.power, &group.currents]
+}
+
+fn format_measurement(m: &Measurement, unit: &str) -> String {
+    let value = match unit {
+        "RPM" => format!("{:>5.0} RPM", m.value),
+        "V" => format!("{:>5.2} V", m.value),
+        "W" => format!("{:>5.1} W", m.value),
+        _ => format!("{:>5.2} {unit}", m.value),
+    };
+    match measurement_color(m, unit == "RPM") {
+        Some(color) => format!("<span foreground=\"{color}\">{value}</span>"),
+        None => value,
+    }
 }
 
-fn render_section(tooltip: &mut String, header: &str, labels: &[(&str, f64)], kind: SensorKind, pad_width: usize) {
+/// Fan, voltage, power and current lines for one device, after its temperatures.
+fn render_measurements(tooltip: &mut String, options: &SensorsOptions, group: &SensorGroup, pad_width: usize) {
+    for (readings, unit) in measurements(group).iter().zip(MEASUREMENT_UNITS) {
+        for m in readings.iter() {
+            let Some(label) = display_label(options, &group.name, &m.label) else {
+                continue;
+            };
+            tooltip.push_str(&format!(
+                "  {: <pad$} {}\n",
+                crate::pango::escape(&label),
+                format_measurement(m, unit),
+                pad = pad_width
+            ));
+        }
+    }
+}
+
+/// Find the bar's power or fan reading: the selected one, else `fallback`'s pick.
+fn select_measurement<'a>(
+    data: &'a SensorData,
+    selector: &Selector,
+    kind: fn(&SensorGroup) -> &Vec<Measurement>,
+    fallback: fn(&SensorGroup, &Measurement) -> bool,
+) -> Option<&'a Measurement> {
+    let all = || data.sensors.iter().flat_map(|g| kind(g).iter().map(move |m| (g, m)));
+    let pick = if selector.chip.is_some() || selector.label.is_some() {
+        all().find(|(g, m)| selector.matches(&g.name, &m.label))
+    } else {
+        all().find(|(g, m)| fallback(g, m)).or_else(|| all().next())
+    };
+    pick.map(|(_, m)| m)
+}
+
+fn render_section(
+    tooltip: &mut String,
+    header: &str,
+    labels: &[(String, &SensorReading, Thresholds)],
+    show_limits: bool,
+    pad_width: usize,
+) {
     tooltip.push_str(&format!("\n<b>{}</b>\n", crate::pango::escape(header)));
-    for &(label, temp) in labels {
+    for (label, reading, t) in labels {
+        let history = match &reading.history {
+            Some(history) => format_history(history, temp_color(reading.temp, *t)),
+            None => String::new(),
+        };
         tooltip.push_str(&format!(
-            "  {: <pad$} {}\n",
+            "  {: <pad$} {}{history}\n",
             crate::pango::escape(label),
-            format_temp(temp, kind),
+            format_temp(reading.temp, *t, show_limits),
             pad = pad_width
         ));
     }
 }
 
+/// A group's visible readings with their display labels and color bands, ready for `render_section`.
+fn group_labels<'a>(
+    options: &SensorsOptions,
+    group: &'a SensorGroup,
+    kind: SensorKind,
+) -> Vec<(String, &'a SensorReading, Thresholds)> {
+    group
+        .readings
+        .iter()
+        .filter_map(|r| {
+            let label = display_label(options, &group.name, &r.label)?;
+            Some((label, r, thresholds(options, &group.name, r, kind)))
+        })
+        .collect()
+}
+
+/// A device's section: its temperatures, then everything else, then `extra` lines (GPU stats).
+/// Devices with nothing left to show after the hide rules are skipped.
+fn render_group(
+    tooltip: &mut String,
+    options: &SensorsOptions,
+    header: &str,
+    group: &SensorGroup,
+    kind: SensorKind,
+    extra: &[String],
+    pad: usize,
+) {
+    let labels = group_labels(options, group, kind);
+    let has_measurements = measurements(group)
+        .iter()
+        .flat_map(|ms| ms.iter())
+        .any(|m| display_label(options, &group.name, &m.label).is_some());
+    if labels.is_empty() && !has_measurements && extra.is_empty() {
+        return;
+    }
+    render_section(tooltip, header, &labels, options.show_limits, pad);
+    render_measurements(tooltip, options, group, pad);
+    tooltip.push_str(&extra.concat());
+}
+
+/// Tooltip lines for a GPU's own stats, matching hide and rename rules with the driver as chip
+/// (`nvidia:Clocks`). `standalone` adds the temperature, fan and power lines, which a GPU with an
+/// hwmon section already shows there.
+fn gpu_lines(options: &SensorsOptions, gpu: &Gpu, standalone: bool, pad: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = |label: &str, value: String| {
+        if let Some(label) = display_label(options, &gpu.driver, label) {
+            lines.push(format!("  {: <pad$} {value}\n", crate::pango::escape(&label)));
+        }
+    };
+    if let Some(temp) = gpu.temp.filter(|_| standalone) {
+        // nvidia-smi reports no limits here, so only an override can change the bands
+        let bands = options
+            .limits
+            .iter()
+            .find(|(sel, _)| sel.matches(&gpu.driver, "GPU"))
+            .map_or(SensorKind::CpuGpu.thresholds(), |(_, t)| *t);
+        line("GPU", format_temp(temp, bands, options.show_limits));
+    }
+    if let Some(utilization) = gpu.utilization {
+        line("Load", format!("{utilization:>5.0} %"));
+    }
+    if let Some(fan) = gpu.fan.filter(|_| standalone) {
+        line("Fan", format!("{fan:>5.0} %"));
+    }
+    if let (Some(used), Some(total)) = (gpu.memory_used, gpu.memory_total) {
+        line("VRAM", format!("{:>5.1}/{:.1} GiB", used / 1024.0, total / 1024.0));
+    }
+    if let Some(draw) = gpu.power_draw.filter(|_| standalone) {
+        let m = Measurement {
+            label: String::new(),
+            value: draw,
+            min: None,
+            max: gpu.power_limit,
+            crit: None,
+        };
+        let limit = match gpu.power_limit {
+            Some(limit) => format!(" <span foreground=\"#949cbb\">/ {limit:.0} W</span>"),
+            None => String::new(),
+        };
+        line("Power", format!("{}{limit}", format_measurement(&m, "W")));
+    }
+    match (gpu.clock_graphics, gpu.clock_memory) {
+        (Some(graphics), Some(memory)) => line("Clocks", format!("{graphics:>5.0}/{memory:.0} MHz")),
+        (Some(graphics), None) => line("Clock", format!("{graphics:>5.0} MHz")),
+        _ => {}
+    }
+    if let Some(rc6) = gpu.rc6 {
+        line("RC6", format!("{rc6:>5.0} %"));
+    }
+    lines
+}
+
+/// The GPU behind an hwmon group, matched by PCI address.
+fn group_gpu<'a>(data: &'a SensorData, group: &SensorGroup) -> Option<&'a Gpu> {
+    let id = group.id.as_deref()?;
+    data.gpus
+        .iter()
+        .find(|gpu| id.strip_prefix("pci:") == Some(gpu.bus_id.as_str()))
+}
+
 pub fn parse_data(raw_data: Value) -> Result<String, Box<dyn std::error::Error>> {
     let data = from_value::<SensorData>(raw_data)?;
+    let options = SensorsOptions::from_env();
 
     // Find CPU temp for bar text (k10temp Tctl for AMD, or first coretemp reading for Intel)
     let cpu_temp = data
         .sensors
         .iter()
         .find(|g| g.name == "k10temp" || g.name == "coretemp")
-        .and_then(|g| g.readings.iter().find(|r| r.label == "Tctl").or(g.readings.first()))
-        .map(|r| r.temp);
-
-    // Bar text: thermometer emoji + CPU temp on a single line
-    let text = match cpu_temp {
-        Some(t) => {
-            let color = temp_color(t, SensorKind::CpuGpu);
-            format!("<span size=\"x-small\">\u{F050F} <span foreground=\"{color}\">{t:.0}°</span></span>")
+        .and_then(|g| {
+            let r = g.readings.iter().find(|r| r.label == "Tctl").or(g.readings.first())?;
+            Some((r, thresholds(&options, &g.name, r, SensorKind::CpuGpu)))
+        });
+
+    // Bar text: icon + one reading on a single line (CPU temp by default)
+    let bar_reading = |icon: &str, m: Option<&Measurement>, unit: &str, fan: bool| match m {
+        Some(m) => {
+            let color = measurement_color(m, fan).unwrap_or("#babbf1");
+            format!(
+                "<span size=\"x-small\">{icon} <span foreground=\"{color}\">{:.0}{unit}</span></span>",
+                m.value
+            )
+        }
+        None => format!("<span size=\"x-small\">{icon} <span foreground=\"#949cbb\">--{unit}</span></span>"),
+    };
+    let text = match &options.bar {
+        BarItem::Temp => match cpu_temp {
+            Some((r, bands)) => {
+                let color = temp_color(r.temp, bands);
+                let trend = match r.history.as_ref().and_then(|h| h.trend).filter(|_| options.trend) {
+                    Some(delta) if delta >= TREND_THRESHOLD => "↑",
+                    Some(delta) if delta <= -TREND_THRESHOLD => "↓",
+                    _ => "",
+                };
+                format!(
+                    "<span size=\"x-small\">\u{F050F} <span foreground=\"{color}\">{:.0}°{trend}</span></span>",
+                    r.temp
+                )
+            }
+            None => "<span size=\"x-small\">\u{F050F} <span foreground=\"#949cbb\">--°</span></span>".to_string(),
+        },
+        // Package power: CPU chips (zenpower, k10temp, coretemp) first, then whatever reports power
+        BarItem::Power(selector) => {
+            let power = select_measurement(
+                &data,
+                selector,
+                |g| &g.power,
+                |g, _| ["zenpower", "k10temp", "coretemp"].contains(&g.name.as_str()),
+            );
+            bar_reading("\u{F140B}", power, "W", false) // 󱐋 nf-md-lightning_bolt
+        }
+        BarItem::Fan(selector) => {
+            let fan = select_measurement(&data, selector, |g| &g.fans, |_, m| m.value > 0.0);
+            bar_reading("\u{F0210}", fan, "", true) // 󰈐 nf-md-fan
         }
-        None => "<span size=\"x-small\">\u{F050F} <span foreground=\"#949cbb\">--°</span></span>".to_string(),
     };
 
     // Tooltip: rich sensor dashboard
@@ -135,77 +340,142 @@ pub fn parse_data(raw_data: Value) -> Result<String, Box<dyn std::error::Error>>
     // Compute dynamic padding: find the longest label across all sensors
     let mut max_label_len = 3_usize; // minimum "GPU"
     for group in &data.sensors {
-        for r in &group.readings {
-            max_label_len = max_label_len.max(r.label.len());
+        let labels = group.readings.iter().map(|r| &r.label);
+        let labels = labels.chain(
+            measurements(group)
+                .into_iter()
+                .flat_map(|ms| ms.iter().map(|m| &m.label)),
+        );
+        for label in labels.filter_map(|l| display_label(&options, &group.name, l)) {
+            max_label_len = max_label_len.max(label.chars().count());
+        }
+        // Named DIMMs show their name in the label column
+        let ram = options
+            .catalog
+            .lookup(&group.name)
+            .is_some_and(|i| matches!(options.catalog.entries[i].kind, SensorKind::Ram));
+        if let Some(name) = device_name(&options, group).filter(|_| ram) {
+            max_label_len = max_label_len.max(name.chars().count());
         }
     }
     // Account for DIMM numbering ("DIMM XX") and GPU numbering ("GPU X")
     max_label_len = max_label_len.max(7);
     let pad = max_label_len + 2; // add some breathing room
 
-    // Render known sensor categories in defined order
-    for &(_, display_title, pattern, kind, prefix) in KNOWN_SENSORS {
+    // GPU stats shown under the GPU's own hwmon section
+    let gpu_extra = |group: &SensorGroup| match group_gpu(&data, group) {
+        Some(gpu) => gpu_lines(&options, gpu, false, pad),
+        None => Vec::new(),
+    };
+
+    // Render cataloged chips in catalog order
+    let catalog = &options.catalog;
+    for (index, entry) in catalog.entries.iter().enumerate() {
         let groups: Vec<_> = data
             .sensors
             .iter()
-            .filter(|g| sensor_matches(&g.name, pattern, prefix))
+            .filter(|g| catalog.lookup(&g.name) == Some(index))
             .collect();
         if groups.is_empty() {
             continue;
         }
+        let header = entry.header();
+        // Named devices (an alias, or a model like "Samsung SSD 990 PRO 2TB (nvme0)") are headed by
+        // their name; the rest by the catalog title, numbered when there are several
+        let named = |group: &SensorGroup| {
+            device_name(&options, group).map(|name| match entry.icon.as_str() {
+                "" => name.to_string(),
+                icon => format!("{icon} {name}"),
+            })
+        };
 
         if groups.len() == 1 {
-            let labels: Vec<_> = groups[0].readings.iter().map(|r| (r.label.as_str(), r.temp)).collect();
-            render_section(&mut tooltip, display_title, &labels, kind, pad);
-        } else if pattern == "spd5118" {
+            let header = named(groups[0]).unwrap_or(header);
+            render_group(
+                &mut tooltip,
+                &options,
+                &header,
+                groups[0],
+                entry.kind,
+                &gpu_extra(groups[0]),
+                pad,
+            );
+        } else if matches!(entry.kind, SensorKind::Ram) {
             // RAM DIMMs: single header, one line per DIMM
             let labels: Vec<_> = groups
                 .iter()
                 .enumerate()
-                .filter_map(|(i, g)| g.readings.first().map(|r| (i, r.temp)))
+                .filter_map(|(i, g)| {
+                    let r = g.readings.first()?;
+                    display_label(&options, &g.name, &r.label)?;
+                    let label = device_name(&options, g).map_or_else(|| format!("DIMM {}", i + 1), str::to_string);
+                    Some((label, r, thresholds(&options, &g.name, r, entry.kind)))
+                })
                 .collect();
-            tooltip.push_str(&format!("\n<b>{display_title}</b>\n"));
-            for (i, temp) in &labels {
-                let dimm_label = format!("DIMM {}", i + 1);
-                tooltip.push_str(&format!(
-                    "  {: <pad$} {}\n",
-                    dimm_label,
-                    format_temp(*temp, kind),
-                    pad = pad,
-                ));
+            if !labels.is_empty() {
+                render_section(&mut tooltip, &header, &labels, options.show_limits, pad);
             }
         } else {
-            // Multiple devices with same name: numbered headers
+            // Multiple devices with same name: named or numbered headers
             for (i, group) in groups.iter().enumerate() {
-                let header = format!("{display_title} {}", i + 1);
-                let labels: Vec<_> = group.readings.iter().map(|r| (r.label.as_str(), r.temp)).collect();
-                render_section(&mut tooltip, &header, &labels, kind, pad);
+                let header = named(group).unwrap_or_else(|| format!("{header} {}", i + 1));
+                render_group(
+                    &mut tooltip,
+                    &options,
+                    &header,
+                    group,
+                    entry.kind,
+                    &gpu_extra(group),
+                    pad,
+                );
             }
         }
     }
 
-    // NVIDIA GPU section
-    if !data.nvidia.is_empty() {
-        let kind = SensorKind::CpuGpu;
-        if data.nvidia.len() == 1 {
-            let labels = vec![("GPU", data.nvidia[0])];
-            render_section(&mut tooltip, "\u{F08AE} GPU NVIDIA", &labels, kind, pad);
-        } else {
-            tooltip.push_str("\n<b>\u{F08AE} GPU NVIDIA</b>\n");
-            for (i, &temp) in data.nvidia.iter().enumerate() {
-                let label = format!("GPU {i}");
-                tooltip.push_str(&format!("  {: <pad$} {}\n", label, format_temp(temp, kind), pad = pad,));
-            }
+    // GPUs without an hwmon section (NVIDIA, integrated Intel): a section each
+    let standalone: Vec<&Gpu> = data
+        .gpus
+        .iter()
+        .filter(|gpu| {
+            !data
+                .sensors
+                .iter()
+                .any(|g| group_gpu(&data, g).is_some_and(|m| m.bus_id == gpu.bus_id))
+        })
+        .collect();
+    for gpu in &standalone {
+        let id = format!("pci:{}", gpu.bus_id);
+        let header = match options.aliases.iter().find(|(alias_id, _)| *alias_id == id) {
+            Some((_, alias)) => alias.clone(),
+            // Identical cards are told apart by nvidia-smi index, or card number for DRM
+            None if standalone.iter().filter(|other| other.name == gpu.name).count() > 1 => match gpu.driver.as_str() {
+                "nvidia" => format!("{} (GPU {})", gpu.name, gpu.index),
+                _ => format!("{} (card{})", gpu.name, gpu.index),
+            },
+            None => gpu.name.clone(),
+        };
+        let lines = gpu_lines(&options, gpu, true, pad);
+        if !lines.is_empty() {
+            tooltip.push_str(&format!("\n<b>\u{F08AE} {}</b>\n", crate::pango::escape(&header)));
+            tooltip.push_str(&lines.concat());
         }
     }
 
     // Any unknown/other sensors
     for group in &data.sensors {
-        if is_known_sensor(&group.name) {
+        if catalog.lookup(&group.name).is_some() {
             continue;
         }
-        let labels: Vec<_> = group.readings.iter().map(|r| (r.label.as_str(), r.temp)).collect();
-        render_section(&mut tooltip, &group.name, &labels, SensorKind::Motherboard, pad);
+        let header = device_name(&options, group).unwrap_or(&group.name);
+        render_group(
+            &mut tooltip,
+            &options,
+            header,
+            group,
+            SensorKind::Motherboard,
+            &gpu_extra(group),
+            pad,
+        );
     }
 
     Ok(serde_json::to_string(&json!({
@@ -1,55 +1,113 @@
 use std::fs;
-use std::process::Command;
-use std::sync::Mutex;
-use std::time::{Duration, Instant};
+use std::path::Path;
 
-use super::{SensorData, SensorGroup, SensorReading};
+use super::{Measurement, SensorData, SensorGroup, SensorReading};
 
-// nvidia-smi is expensive (~100ms per call), so we cache its result and only
-// re-query every NVIDIA_INTERVAL seconds. The hwmon sysfs reads are virtually
-// free (kernel virtual filesystem) and run every time.
-const NVIDIA_INTERVAL: Duration = Duration::from_secs(10);
+pub(super) fn read_number(path: &Path) -> Option<f64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
 
-static NVIDIA_CACHE: Mutex<(Vec<f64>, Option<Instant>)> = Mutex::new((Vec::new(), None));
+fn read_label(dir: &Path, prefix: &str, n: u32) -> Option<String> {
+    fs::read_to_string(dir.join(format!("{prefix}{n}_label")))
+        .ok()
+        .map(|s| s.trim().to_string())
+}
 
-fn query_nvidia() -> Vec<f64> {
-    let mut cache = NVIDIA_CACHE.lock().unwrap();
-    let stale = cache.1.map(|t| t.elapsed() >= NVIDIA_INTERVAL).unwrap_or(true);
-    if !stale {
-        return cache.0.clone();
-    }
+/// Channel numbers with a `{prefix}{n}_{suffix}` file, ascending: hwmon numbering has gaps
+/// (nct chips start voltages at in0, temps at temp1, and skip unwired channels).
+fn channels(files: &[String], prefix: &str, suffix: &str) -> Vec<u32> {
+    let mut found: Vec<u32> = files
+        .iter()
+        .filter_map(|f| {
+            f.strip_prefix(prefix)?
+                .strip_suffix(suffix)?
+                .strip_suffix('_')?
+                .parse()
+                .ok()
+        })
+        .collect();
+    found.sort();
+    found.dedup();
+    found
+}
 
-    // Run nvidia-smi in a thread with a 5-second timeout to avoid blocking
-    // the scheduler forever if nvidia-smi hangs (driver bug, GPU lockup).
-    let (tx, rx) = std::sync::mpsc::channel::<Vec<f64>>();
-    std::thread::spawn(move || {
-        let result = Command::new("nvidia-smi")
-            .args(["--query-gpu=temperature.gpu", "--format=csv,noheader,nounits"])
-            .output()
-            .ok()
-            .and_then(|out| {
-                if out.status.success() {
-                    let stdout = String::from_utf8(out.stdout).ok()?;
-                    Some(stdout.lines().filter_map(|l| l.trim().parse().ok()).collect())
-                } else {
-                    None
-                }
+/// Non-temperature readings of one kind. `inputs` are the value files in order of preference
+/// (amdgpu has `power1_average` on older kernels, `power1_input` on newer ones), `scale` converts
+/// sysfs units (mV, µW, mA) into V/W/A.
+fn read_measurements(dir: &Path, files: &[String], prefix: &str, inputs: &[&str], scale: f64) -> Vec<Measurement> {
+    let mut all: Vec<u32> = inputs.iter().flat_map(|input| channels(files, prefix, input)).collect();
+    all.sort();
+    all.dedup();
+    all.into_iter()
+        .filter_map(|n| {
+            let file = |suffix: &str| dir.join(format!("{prefix}{n}_{suffix}"));
+            let value = inputs.iter().find_map(|input| read_number(&file(input)))? / scale;
+            let label = read_label(dir, prefix, n);
+            // Unwired fan headers read 0 RPM and have no label
+            if prefix == "fan" && value == 0.0 && label.is_none() {
+                return None;
+            }
+            let limit = |suffixes: &[&str]| suffixes.iter().find_map(|s| read_number(&file(s))).map(|v| v / scale);
+            Some(Measurement {
+                label: label.unwrap_or_else(|| format!("{prefix}{n}")),
+                value,
+                min: limit(&["min"]),
+                // amdgpu reports its power limit as power1_cap
+                max: limit(&["max", "cap"]),
+                crit: limit(&["crit"]),
             })
-            .unwrap_or_default();
-        let _ = tx.send(result);
-    });
+        })
+        .collect()
+}
 
-    let temps = rx.recv_timeout(Duration::from_secs(5)).unwrap_or_default();
-    *cache = (temps.clone(), Some(Instant::now()));
-    temps
+/// Stable id and display name of a hwmon chip's parent device, from its `device` link. hwmonN
+/// and nvmeN numbering can change between boots, so NVMe drives are keyed by serial and SCSI disks
+/// by WWID; everything else by bus and address ("pci:0000:03:00.0", "i2c:1-0051").
+fn device_identity(hwmon: &Path) -> (Option<String>, Option<String>) {
+    let device = hwmon.join("device");
+    let Ok(target) = fs::canonicalize(&device) else {
+        return (None, None); // virtual chips without a parent device
+    };
+    let file_name = |p: &Path| p.file_name().map(|n| n.to_string_lossy().into_owned());
+    let Some(kernel_name) = file_name(&target) else {
+        return (None, None);
+    };
+    let read = |file: &str| {
+        fs::read_to_string(device.join(file))
+            .ok()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+    // Only the link's name matters, so its target needn't exist (snapshots skip empty directories)
+    let subsystem = fs::read_link(device.join("subsystem")).ok().and_then(|p| file_name(&p));
+
+    let id = match subsystem.as_deref() {
+        Some("nvme") => read("serial").map(|serial| format!("nvme:{serial}")),
+        Some("scsi") => read("wwid").map(|wwid| format!("scsi:{wwid}")),
+        _ => None,
+    }
+    .or_else(|| subsystem.map(|subsystem| format!("{subsystem}:{kernel_name}")));
+    let name = read("model")
+        .or_else(|| read("product_name"))
+        .map(|model| format!("{model} ({kernel_name})"));
+    (id, name)
 }
 
 pub fn query() -> Option<String> {
+    let mut data = read(&super::options::sysfs_root())?;
+    super::gpu::record_rc6(&mut data.gpus);
+    super::history::record(&mut data);
+    serde_json::to_string(&data).ok()
+}
+
+/// Read sensors from sysfs mounted at `root` ("/sys", or a snapshot).
+pub(super) fn read(root: &Path) -> Option<SensorData> {
     let mut sensors = Vec::new();
 
-    let entries = fs::read_dir("/sys/class/hwmon")
+    let hwmon = root.join("class/hwmon");
+    let entries = fs::read_dir(&hwmon)
         .map_err(|err| {
-            eprintln!("ERROR: failed to read /sys/class/hwmon: {err}");
+            eprintln!("ERROR: failed to read {}: {err}", hwmon.display());
         })
         .ok()?;
 
@@ -59,17 +117,19 @@ pub fn query() -> Option<String> {
             Ok(n) => n.trim().to_string(),
             Err(_) => continue,
         };
+        let files: Vec<String> = match fs::read_dir(&path) {
+            Ok(dir) => dir
+                .flatten()
+                .map(|e| e.file_name().to_string_lossy().into_owned())
+                .collect(),
+            Err(_) => continue,
+        };
 
         let mut readings = Vec::new();
-        for i in 1..=24 {
-            let temp_path = path.join(format!("temp{i}_input"));
-            let temp_str = match fs::read_to_string(&temp_path) {
-                Ok(s) => s,
-                Err(_) => continue,
-            };
-            let temp_mc: f64 = match temp_str.trim().parse() {
-                Ok(v) => v,
-                Err(_) => continue,
+        for i in channels(&files, "temp", "input") {
+            let temp_mc = match read_number(&path.join(format!("temp{i}_input"))) {
+                Some(v) => v,
+                None => continue,
             };
             let temp = temp_mc / 1000.0;
 
@@ -80,23 +140,51 @@ pub fn query() -> Option<String> {
                 continue;
             }
 
-            let label = fs::read_to_string(path.join(format!("temp{i}_label")))
-                .map(|s| s.trim().to_string())
-                .unwrap_or_else(|_| format!("temp{i}"));
+            let label = read_label(&path, "temp", i).unwrap_or_else(|| format!("temp{i}"));
+
+            // Some chips report placeholder limits (0, 127, 255°C) for channels they don't manage
+            let limit = |suffix: &str| {
+                read_number(&path.join(format!("temp{i}_{suffix}")))
+                    .map(|mc| mc / 1000.0)
+                    .filter(|c| *c > 0.0 && *c < 127.0)
+            };
 
-            readings.push(SensorReading { label, temp });
+            readings.push(SensorReading {
+                label,
+                temp,
+                max: limit("max"),
+                crit: limit("crit"),
+                emergency: limit("emergency"),
+                history: None,
+            });
         }
 
-        if !readings.is_empty() {
-            sensors.push(SensorGroup { name, readings });
+        let (id, device) = device_identity(&path);
+        let group = SensorGroup {
+            name,
+            id,
+            device,
+            readings,
+            fans: read_measurements(&path, &files, "fan", &["input"], 1.0),
+            voltages: read_measurements(&path, &files, "in", &["input"], 1000.0),
+            power: read_measurements(&path, &files, "power", &["average", "input"], 1_000_000.0),
+            currents: read_measurements(&path, &files, "curr", &["input"], 1000.0),
+        };
+        let empty = group.readings.is_empty()
+            && group.fans.is_empty()
+            && group.voltages.is_empty()
+            && group.power.is_empty()
+            && group.currents.is_empty();
+        if !empty {
+            sensors.push(group);
         }
     }
 
-    // Sort by name for consistent ordering across reboots
-    sensors.sort_by(|a, b| a.name.cmp(&b.name));
+    // Sort by name, then device id, for consistent ordering across reboots: identical chips
+    // (two NVMe drives, DIMMs) would otherwise be numbered in hwmonN order, which isn't stable
+    sensors.sort_by(|a, b| (&a.name, &a.id).cmp(&(&b.name, &b.id)));
 
-    let nvidia = query_nvidia();
+    let gpus = super::gpu::query(root);
 
-    let data = SensorData { sensors, nvidia };
-    serde_json::to_string(&data).ok()
+    Some(SensorData { sensors, gpus })
 }
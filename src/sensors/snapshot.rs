@@ -0,0 +1,141 @@
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use super::options::sysfs_root;
+
+// What the queries read beyond each hwmon directory's own files, relative to the hwmon directory
+// (see `device_identity` in query.rs) and to each DRM card (gpu.rs)
+const DEVICE_FILES: &[&str] = &["subsystem", "model", "serial", "wwid", "product_name"];
+const CARD_FILES: &[&str] = &["gt_act_freq_mhz", "gt_cur_freq_mhz", "power/rc6_residency_ms"];
+const CARD_DEVICE_FILES: &[&str] = &[
+    "driver",
+    "product_name",
+    "gpu_busy_percent",
+    "mem_info_vram_used",
+    "mem_info_vram_total",
+    "pp_dpm_sclk",
+    "pp_dpm_mclk",
+    "tile0/gt0/freq0/act_freq",
+    "tile0/gt0/freq0/cur_freq",
+    "tile0/gt0/gtidle/idle_residency_ms",
+];
+
+/// `base/target` with `..` resolved lexically. That's how sysfs's relative links resolve, because
+/// the directories they are in are real ones.
+fn resolve(base: &Path, target: &Path) -> PathBuf {
+    let mut resolved = PathBuf::new();
+    for component in base.join(target).components() {
+        match component {
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            Component::CurDir => {}
+            other => resolved.push(other),
+        }
+    }
+    resolved
+}
+
+/// Copy `rel` (relative to the sysfs roots) from `src` to `dst`, recreating each symlink on the
+/// way and following it, so the copy resolves the same way the original does.
+fn mirror(src: &Path, dst: &Path, rel: &Path) -> io::Result<()> {
+    let mut prefix = PathBuf::new();
+    let mut components = rel.components();
+    while let Some(component) = components.next() {
+        prefix.push(component);
+        if !fs::symlink_metadata(src.join(&prefix))?.file_type().is_symlink() {
+            continue;
+        }
+        let target = fs::read_link(src.join(&prefix))?;
+        if target.is_absolute() {
+            return Err(io::Error::other("absolute symlink"));
+        }
+        let link = dst.join(&prefix);
+        if fs::symlink_metadata(&link).is_err() {
+            fs::create_dir_all(dst.join(prefix.parent().unwrap_or(Path::new(""))))?;
+            std::os::unix::fs::symlink(&target, &link)?;
+        }
+        let resolved = resolve(prefix.parent().unwrap_or(Path::new("")), &target);
+        return mirror(src, dst, &resolved.join(components.as_path()));
+    }
+
+    if fs::metadata(src.join(rel))?.is_dir() {
+        return fs::create_dir_all(dst.join(rel));
+    }
+    let contents = fs::read(src.join(rel))?;
+    if let Some(parent) = rel.parent() {
+        fs::create_dir_all(dst.join(parent))?;
+    }
+    fs::write(dst.join(rel), contents)
+}
+
+/// Entries of a sysfs class directory, like `class/hwmon/hwmon0`, relative to `root`.
+fn class_entries(root: &Path, class: &str) -> Vec<PathBuf> {
+    let mut entries: Vec<PathBuf> = match fs::read_dir(root.join(class)) {
+        Ok(dir) => dir.flatten().map(|e| Path::new(class).join(e.file_name())).collect(),
+        Err(_) => Vec::new(),
+    };
+    entries.sort();
+    entries
+}
+
+/// `waybap sensors snapshot <dir>`: copy the hwmon and DRM files the sensors query reads into
+/// `dir`, symlinks included, so `WAYBAP_SENSORS_SYSFS_ROOT=<dir>` reads it back like this machine.
+pub fn snapshot(dir: &str) -> Result<(), ()> {
+    let root = sysfs_root();
+    let dst = Path::new(dir);
+    let mut copied = 0;
+    let mut capture = |rel: PathBuf| match mirror(&root, dst, &rel) {
+        Ok(()) => copied += 1,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => eprintln!("WARNING: skipped {}: {err}", rel.display()),
+    };
+
+    let hwmons = class_entries(&root, "class/hwmon");
+    if hwmons.is_empty() {
+        eprintln!("ERROR: no hwmon devices in {}", root.join("class/hwmon").display());
+        return Err(());
+    }
+    for hwmon in &hwmons {
+        // The chip's own attributes are the regular files; `device`, `subsystem` and `power/` aren't
+        let files: Vec<PathBuf> = match fs::read_dir(root.join(hwmon)) {
+            Ok(dir) => dir
+                .flatten()
+                .filter(|e| e.file_type().is_ok_and(|t| t.is_file()))
+                .map(|e| hwmon.join(e.file_name()))
+                .collect(),
+            Err(err) => {
+                eprintln!("WARNING: skipped {}: {err}", hwmon.display());
+                continue;
+            }
+        };
+        files.into_iter().for_each(&mut capture);
+        for file in DEVICE_FILES {
+            capture(hwmon.join("device").join(file));
+        }
+    }
+
+    for card in class_entries(&root, "class/drm") {
+        // card0, card1; not connectors (card0-DP-1) or render nodes
+        let is_card = card
+            .file_name()
+            .and_then(|n| n.to_str()?.strip_prefix("card"))
+            .is_some_and(|n| n.parse::<u32>().is_ok());
+        if !is_card {
+            continue;
+        }
+        for file in CARD_FILES {
+            capture(card.join(file));
+        }
+        for file in CARD_DEVICE_FILES {
+            capture(card.join("device").join(file));
+        }
+        for hwmon in class_entries(&root, &card.join("device/hwmon").to_string_lossy()) {
+            capture(hwmon);
+        }
+    }
+
+    println!("Copied {copied} files from {} into {dir}", root.display());
+    Ok(())
+}
@@ -0,0 +1,294 @@
+//! Fixture tests: `tests/fixtures/sensors/<machine>` are sysfs trees in the shape
+//! `waybap sensors snapshot` writes, read by the real query and rendered by `parse_data`.
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use super::gpu::{parse_nvidia, rc6_percent, run_nvidia_smi};
+use super::history::record;
+use super::parse_data;
+use super::query::read;
+use super::SensorData;
+
+fn read_fixture(machine: &str) -> SensorData {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/sensors")
+        .join(machine);
+    read(&root).expect("fixture has hwmon devices")
+}
+
+fn query(machine: &str) -> Value {
+    serde_json::to_value(read_fixture(machine)).unwrap()
+}
+
+/// Rendered bar text and tooltip of query output, with Pango markup stripped.
+fn render_data(data: Value) -> (String, String) {
+    let output: Value = serde_json::from_str(&parse_data(data).unwrap()).unwrap();
+    let text = |key: &str| {
+        let mut plain = String::new();
+        let mut in_tag = false;
+        for c in output[key].as_str().unwrap().chars() {
+            match c {
+                '<' => in_tag = true,
+                '>' => in_tag = false,
+                c if !in_tag => plain.push(c),
+                _ => {}
+            }
+        }
+        plain
+    };
+    (text("text"), text("tooltip"))
+}
+
+fn render(machine: &str) -> (String, String) {
+    render_data(query(machine))
+}
+
+/// Sections as (header, lines), each line's whitespace collapsed ("DIMM 1 38.0°C").
+fn sections(tooltip: &str) -> Vec<(String, Vec<String>)> {
+    tooltip
+        .split("\n\n")
+        .skip(1) // title
+        .map(|block| {
+            let mut lines = block
+                .lines()
+                .map(|l| l.split_whitespace().collect::<Vec<_>>().join(" "));
+            (
+                lines.next().unwrap_or_default(),
+                lines.filter(|l| !l.is_empty()).collect(),
+            )
+        })
+        .collect()
+}
+
+fn headers(tooltip: &str) -> Vec<String> {
+    sections(tooltip).into_iter().map(|(header, _)| header).collect()
+}
+
+fn section(tooltip: &str, header: &str) -> Vec<String> {
+    let found = sections(tooltip).into_iter().find(|(h, _)| h == header);
+    found
+        .unwrap_or_else(|| panic!("no '{header}' section in:\n{tooltip}"))
+        .1
+}
+
+#[test]
+fn known_chips_render_in_catalog_order() {
+    let (_, tooltip) = render("desktop");
+    assert_eq!(
+        headers(&tooltip)[..7],
+        [
+            "\u{F0EE0} CPU",
+            "\u{F08AE} GPU AMD",
+            "\u{F02CA} Samsung SSD 990 PRO 2TB (nvme1)",
+            "\u{F02CA} Samsung SSD 990 PRO 2TB (nvme0)",
+            "\u{F061A} Motherboard",
+            "\u{F035B} RAM",
+            "foo_sensor",
+        ]
+    );
+}
+
+#[test]
+fn dimms_share_one_section_numbered_by_address() {
+    // hwmon6 is 1-0053 and hwmon7 is 1-0051: numbering follows the I2C address, not hwmonN
+    let (_, tooltip) = render("desktop");
+    assert_eq!(section(&tooltip, "\u{F035B} RAM"), ["DIMM 1 38.0°C", "DIMM 2 39.5°C"]);
+}
+
+#[test]
+fn identical_drives_are_named_by_model_in_serial_order() {
+    let data = query("desktop");
+    let ids: Vec<&str> = data["sensors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|g| g["name"] == "nvme")
+        .map(|g| g["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, ["nvme:S7KHNJ0W111111", "nvme:S7KHNJ0W222222"]);
+
+    let (_, tooltip) = render("desktop");
+    assert_eq!(
+        section(&tooltip, "\u{F02CA} Samsung SSD 990 PRO 2TB (nvme1)"),
+        ["Composite 39.9°C"]
+    );
+    assert_eq!(
+        section(&tooltip, "\u{F02CA} Samsung SSD 990 PRO 2TB (nvme0)"),
+        ["Composite 44.9°C"]
+    );
+}
+
+#[test]
+fn identical_drives_without_models_are_numbered() {
+    let (_, tooltip) = render("laptop");
+    assert_eq!(section(&tooltip, "\u{F02CA} NVMe 1"), ["Composite 41.9°C"]);
+    assert_eq!(section(&tooltip, "\u{F02CA} NVMe 2"), ["Composite 37.9°C"]);
+}
+
+#[test]
+fn unknown_chips_render_after_known_ones_under_their_name() {
+    let (_, tooltip) = render("desktop");
+    let headers = headers(&tooltip);
+    let ram = headers.iter().position(|h| h == "\u{F035B} RAM").unwrap();
+    let unknown = headers.iter().position(|h| h == "foo_sensor").unwrap();
+    assert!(unknown > ram);
+    assert_eq!(section(&tooltip, "foo_sensor"), ["temp1 30.0°C"]);
+}
+
+#[test]
+fn disconnected_inputs_are_dropped() {
+    // nct6799: CPUTIN reads 0 m°C, AUXTIN0 is out of range, fan2 is an unlabelled header at 0 RPM
+    let data = query("desktop");
+    let nct = data["sensors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|g| g["name"] == "nct6799")
+        .unwrap();
+    let labels: Vec<&str> = nct["readings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["label"].as_str().unwrap())
+        .collect();
+    assert_eq!(labels, ["SYSTIN"]);
+
+    let (_, tooltip) = render("desktop");
+    assert_eq!(
+        section(&tooltip, "\u{F061A} Motherboard"),
+        ["SYSTIN 34.0°C", "fan1 850 RPM", "in0 1.01 V"]
+    );
+}
+
+#[test]
+fn gpu_stats_join_their_hwmon_section() {
+    let (_, tooltip) = render("desktop");
+    let gpu = section(&tooltip, "\u{F08AE} GPU AMD");
+    assert_eq!(
+        gpu[gpu.len() - 3..],
+        ["Load 87 %", "VRAM 8.0/24.0 GiB", "Clocks 2500/1249 MHz"]
+    );
+}
+
+#[test]
+fn integrated_gpus_get_their_own_section() {
+    let (_, tooltip) = render("laptop");
+    let gpu = section(&tooltip, "\u{F08AE} Intel GPU");
+    // RC6 needs a previous reading, which only `query` keeps
+    assert_eq!(gpu, ["Clock 1450 MHz"]);
+
+    let mut gpus = read_fixture("laptop").gpus;
+    assert_eq!(gpus[0].rc6_residency_ms, Some(123456.0));
+    gpus[0].rc6 = Some(82.4);
+    let mut data = query("laptop");
+    data["gpus"] = serde_json::to_value(&gpus).unwrap();
+    let (_, tooltip) = render_data(data);
+    assert_eq!(section(&tooltip, "\u{F08AE} Intel GPU"), ["Clock 1450 MHz", "RC6 82 %"]);
+}
+
+#[test]
+fn rc6_is_the_idle_share_of_the_time_since_the_previous_reading() {
+    let start = Instant::now();
+    let second = start + Duration::from_secs(1);
+    assert_eq!(rc6_percent((1000.0, start), 1800.0, second), Some(80.0));
+    assert_eq!(rc6_percent((1000.0, start), 3000.0, second), Some(100.0)); // counters can overshoot
+    assert_eq!(rc6_percent((1000.0, start), 1000.0, start), None); // no time has passed
+}
+
+#[test]
+fn bar_shows_the_cpu_temperature() {
+    assert_eq!(render("desktop").0, "\u{F050F} 62°"); // k10temp Tctl
+    assert_eq!(render("laptop").0, "\u{F050F} 55°"); // first coretemp reading
+}
+
+// `nvidia-smi --query-gpu=... --format=csv,noheader,nounits` output captured on a desktop with a
+// second, older card that reports several fields as unsupported
+const NVIDIA_SMI_DESKTOP: &str = "\
+0, NVIDIA GeForce RTX 4090, 00000000:01:00.0, 45, 30, 12, 1024, 24564, 35.50, 450.00, 210, 405
+1, Quadro P400, 00000000:0A:00.0, 38, [N/A], 0, 5, 2048, [N/A], [Not Supported], 139, 405
+";
+
+#[test]
+fn nvidia_smi_lines_become_gpus() {
+    let gpus = parse_nvidia(NVIDIA_SMI_DESKTOP.lines().next().unwrap());
+    assert_eq!(gpus.len(), 1);
+    let gpu = &gpus[0];
+    assert_eq!((gpu.driver.as_str(), gpu.index), ("nvidia", 0));
+    assert_eq!(gpu.name, "NVIDIA GeForce RTX 4090");
+    assert_eq!(gpu.bus_id, "0000:01:00.0"); // PCI domain trimmed to sysfs's 4 digits
+    assert_eq!(
+        [gpu.temp, gpu.fan, gpu.utilization, gpu.memory_used, gpu.memory_total],
+        [Some(45.0), Some(30.0), Some(12.0), Some(1024.0), Some(24564.0)]
+    );
+    assert_eq!(
+        [gpu.power_draw, gpu.power_limit, gpu.clock_graphics, gpu.clock_memory],
+        [Some(35.5), Some(450.0), Some(210.0), Some(405.0)]
+    );
+}
+
+#[test]
+fn unsupported_nvidia_fields_are_none() {
+    let gpus = parse_nvidia(NVIDIA_SMI_DESKTOP);
+    assert_eq!(gpus.len(), 2);
+    let gpu = &gpus[1];
+    assert_eq!((gpu.index, gpu.bus_id.as_str()), (1, "0000:0a:00.0"));
+    assert_eq!((gpu.fan, gpu.power_draw, gpu.power_limit), (None, None, None));
+    assert_eq!((gpu.utilization, gpu.clock_graphics), (Some(0.0), Some(139.0)));
+}
+
+#[test]
+fn malformed_nvidia_lines_are_skipped() {
+    let output = format!("No devices were found\n0, NVIDIA GeForce RTX 4090, 00000000:01:00.0\n{NVIDIA_SMI_DESKTOP}");
+    assert_eq!(parse_nvidia(&output).len(), 2);
+}
+
+#[test]
+fn nvidia_smi_is_run_from_path() {
+    use std::os::unix::fs::PermissionsExt;
+
+    // A stub that prints the captured output (with a builtin: PATH is only the stub's directory),
+    // found through the command's own PATH so the real query and its cache never see it
+    let dir = std::env::temp_dir().join(format!("waybap-nvidia-stub-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let stub = dir.join("nvidia-smi");
+    std::fs::write(&stub, format!("#!/bin/sh\nprintf '%s' '{NVIDIA_SMI_DESKTOP}'\n")).unwrap();
+    std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let mut command = std::process::Command::new("nvidia-smi");
+    command.env("PATH", &dir);
+    let gpus = run_nvidia_smi(command);
+
+    let mut missing = std::process::Command::new("nvidia-smi");
+    missing.env("PATH", dir.join("empty"));
+    let none = run_nvidia_smi(missing);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let names: Vec<&str> = gpus.iter().map(|g| g.name.as_str()).collect();
+    assert_eq!(names, ["NVIDIA GeForce RTX 4090", "Quadro P400"]);
+    assert!(none.is_empty());
+}
+
+#[test]
+fn history_tracks_session_stats_and_trend() {
+    // The laptop's coretemp warming from 40° to 79°, one sample per second
+    let mut data = None;
+    for temp in 40..80 {
+        let mut sample = read_fixture("laptop");
+        let coretemp = sample.sensors.iter_mut().find(|g| g.name == "coretemp").unwrap();
+        coretemp.readings[0].temp = temp as f64;
+        record(&mut sample);
+        data = Some(sample);
+    }
+    let data = data.unwrap();
+    let coretemp = data.sensors.iter().find(|g| g.name == "coretemp").unwrap();
+    let history = coretemp.readings[0].history.as_ref().unwrap();
+    assert_eq!((history.min, history.max, history.avg), (40.0, 79.0, 59.5));
+    assert_eq!(history.samples.len(), 12); // 40 samples averaged into the 12-wide sparkline
+    assert_eq!(history.trend, Some(30.0));
+
+    let (_, tooltip) = render_data(serde_json::to_value(data).unwrap());
+    let line = &section(&tooltip, "\u{F0EE0} CPU")[0];
+    assert!(line.ends_with("79.0°C ▁▂▂▃▃▄▅▅▆▇▇█ ↓ 40 ↑ 79 ⌀ 60"), "{line}");
+}
@@ -2,6 +2,7 @@ use std::fs::read_to_string;
 use std::io;
 
 use crate::crypto;
+use crate::markets;
 use crate::pango;
 use crate::scheduler::get_cache_fp;
 use crate::sensors;
@@ -60,6 +61,16 @@ fn serve_request(request: Request) -> io::Result<()> {
     match (request.method(), request.url()) {
         (Method::Get, "/api/weather") => serve_cached_api(request, "weather", weather::parse_data),
         (Method::Get, "/api/crypto") => serve_cached_api(request, "crypto", crypto::parse_data),
+        (Method::Post, "/api/crypto/ack") => {
+            let acknowledged = crypto::acknowledge();
+            serve_json(
+                request,
+                serde_json::json!({ "acknowledged": acknowledged })
+                    .to_string()
+                    .as_bytes(),
+            )
+        }
+        (Method::Get, "/api/markets") => serve_cached_api(request, "markets", markets::parse_data),
         (Method::Get, "/api/sensors") => serve_cached_api(request, "sensors", sensors::parse_data),
         (Method::Get, "/api/usage") => serve_cached_api(request, "usage", usage::parse_data),
         _ => serve_404(request),
@@ -0,0 +1,189 @@
+use std::collections::HashSet;
+use std::process::Command;
+use std::sync::Mutex;
+
+use chrono::{Duration, NaiveDateTime};
+
+use crate::config::{env_flag, env_list, env_parse};
+use crate::weather::locale::{fill, Labels};
+
+// Rule names accepted by WAYBAP_ALERT_RULES (all enabled by default).
+const ALL_RULES: &[&str] = &["thunderstorm", "freezing_rain", "snow", "gusts", "frost"];
+
+/// One forecast hour, as seen by the alert engine.
+pub struct HourSample<'a> {
+    pub time: &'a str, // "YYYY-MM-DDTHH:MM", location-local
+    pub code: i32,
+    pub temp: f64,
+    pub snowfall: f64, // cm
+    pub gusts: f64,    // km/h
+}
+
+pub struct Alert {
+    pub rule: &'static str,
+    pub message: String,
+    start: String, // time of the first matching hour, used to dedupe notifications
+}
+
+struct AlertRules {
+    enabled: Vec<String>,
+    hours: usize,
+    gust_kmh: f64,
+    snow_cm: f64,
+}
+
+impl AlertRules {
+    fn from_env() -> AlertRules {
+        let enabled =
+            env_list("WAYBAP_ALERT_RULES").unwrap_or_else(|| ALL_RULES.iter().map(|r| r.to_string()).collect());
+        for rule in &enabled {
+            if !ALL_RULES.contains(&rule.as_str()) {
+                eprintln!(
+                    "WAYBAP_ALERT_RULES: unknown rule '{rule}' (known: {})",
+                    ALL_RULES.join(", ")
+                );
+            }
+        }
+        AlertRules {
+            enabled,
+            hours: env_parse("WAYBAP_ALERT_HOURS", 12),
+            gust_kmh: env_parse("WAYBAP_ALERT_GUST_KMH", 60.0),
+            snow_cm: env_parse("WAYBAP_ALERT_SNOW_CM", 5.0),
+        }
+    }
+
+    fn is_enabled(&self, rule: &str) -> bool {
+        self.enabled.iter().any(|r| r == rule)
+    }
+}
+
+fn hour_of(time: &str) -> &str {
+    time.split('T').nth(1).unwrap_or("??:??")
+}
+
+/// Evaluate the configured rules over the samples starting within WAYBAP_ALERT_HOURS of `now`
+/// (`samples` must start at the current hour). The window is a time span, not a sample count,
+/// so it means the same for 3-hourly (wttr) and 6-hourly (MET's tail) steps.
+pub fn evaluate(samples: &[HourSample], now: NaiveDateTime, labels: &Labels) -> Vec<Alert> {
+    let rules = AlertRules::from_env();
+    let end = now + Duration::hours(rules.hours as i64);
+    let len = samples
+        .iter()
+        .take_while(|s| NaiveDateTime::parse_from_str(s.time, "%Y-%m-%dT%H:%M").is_ok_and(|t| t <= end))
+        .count();
+    let window = &samples[..len];
+    let mut alerts = Vec::new();
+    let Some(first) = window.first() else {
+        return alerts;
+    };
+
+    if rules.is_enabled("thunderstorm") {
+        if let Some(s) = window.iter().find(|s| (95..=99).contains(&s.code)) {
+            alerts.push(Alert {
+                rule: "thunderstorm",
+                message: fill(labels.alert_thunderstorm, &[("time", hour_of(s.time))]),
+                start: s.time.to_string(),
+            });
+        }
+    }
+
+    if rules.is_enabled("freezing_rain") {
+        if let Some(s) = window.iter().find(|s| s.code == 66 || s.code == 67) {
+            alerts.push(Alert {
+                rule: "freezing_rain",
+                message: fill(labels.alert_freezing_rain, &[("time", hour_of(s.time))]),
+                start: s.time.to_string(),
+            });
+        }
+    }
+
+    if rules.is_enabled("snow") {
+        let total: f64 = window.iter().map(|s| s.snowfall).sum();
+        if total >= rules.snow_cm {
+            let start = window.iter().find(|s| s.snowfall > 0.0).unwrap_or(first);
+            alerts.push(Alert {
+                rule: "snow",
+                message: fill(
+                    labels.alert_snow,
+                    &[("cm", &format!("{total:.1}")), ("hours", &rules.hours.to_string())],
+                ),
+                start: start.time.to_string(),
+            });
+        }
+    }
+
+    if rules.is_enabled("gusts") {
+        // Dedupe on the first hour over the threshold — the peak hour can shift between runs.
+        if let Some(onset) = window.iter().find(|s| s.gusts >= rules.gust_kmh) {
+            let peak = window
+                .iter()
+                .max_by(|a, b| a.gusts.total_cmp(&b.gusts))
+                .unwrap_or(onset);
+            alerts.push(Alert {
+                rule: "gusts",
+                message: fill(
+                    labels.alert_gusts,
+                    &[
+                        ("speed", &(peak.gusts.round() as i32).to_string()),
+                        ("time", hour_of(peak.time)),
+                    ],
+                ),
+                start: onset.time.to_string(),
+            });
+        }
+    }
+
+    // @NOTE: Only the first crossing is reported — a temperature hovering around 0° would
+    //   otherwise produce one alert per hour.
+    if rules.is_enabled("frost") {
+        let starts_above = first.temp > 0.0;
+        if let Some(s) = window.iter().find(|s| (s.temp > 0.0) != starts_above) {
+            let template = if starts_above {
+                labels.alert_frost_below
+            } else {
+                labels.alert_frost_above
+            };
+            let message = fill(template, &[("time", hour_of(s.time))]);
+            alerts.push(Alert {
+                rule: "frost",
+                message,
+                start: s.time.to_string(),
+            });
+        }
+    }
+
+    alerts
+}
+
+// Alerts we already sent a notification for (rule + start hour).
+static NOTIFIED: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+
+/// Send a desktop notification (via `notify-send`) for alerts that weren't active on the previous run.
+/// Opt-in with WAYBAP_ALERT_NOTIFY=1.
+pub fn notify(alerts: &[Alert], labels: &Labels) {
+    if !env_flag("WAYBAP_ALERT_NOTIFY", false) {
+        return;
+    }
+    let mut notified = NOTIFIED.lock().unwrap();
+    let previous = notified.take().unwrap_or_default();
+    let mut current = HashSet::new();
+    for alert in alerts {
+        let key = format!("{}@{}", alert.rule, alert.start);
+        if !previous.contains(&key) {
+            let result = Command::new("notify-send")
+                .args([
+                    "--app-name=waybap",
+                    "--urgency=critical",
+                    labels.alert_title,
+                    &alert.message,
+                ])
+                .status();
+            if let Err(err) = result {
+                eprintln!("ERROR: failed to run notify-send: {err}");
+            }
+        }
+        current.insert(key);
+    }
+    // Forget alerts that cleared, so they notify again if they come back.
+    *notified = Some(current);
+}
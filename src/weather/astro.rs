@@ -0,0 +1,249 @@
+//! Sun and moon times computed locally from coordinates — no extra API calls.
+//! A port of the suncalc equations (Agafonkin, after Meeus / NOAA): accurate to about a minute
+//! for the sun and a few minutes for the moon, which is plenty for a tooltip.
+use std::f64::consts::PI;
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime};
+
+const RAD: f64 = PI / 180.0;
+const J1970: f64 = 2440588.0;
+const J2000: f64 = 2451545.0;
+const J0: f64 = 0.0009;
+const OBLIQUITY: f64 = RAD * 23.4397; // of the Earth's axis
+
+/// Sun altitudes (degrees) for the events we show.
+const SUNRISE_ALTITUDE: f64 = -0.833; // upper limb on the horizon, with refraction
+const CIVIL_TWILIGHT_ALTITUDE: f64 = -6.0;
+const GOLDEN_HOUR_ALTITUDE: f64 = 6.0;
+
+/// Sun events for one location-local date. `None` when the sun never crosses that altitude
+/// (polar day/night).
+pub struct SunTimes {
+    pub sunrise: Option<NaiveDateTime>,
+    pub sunset: Option<NaiveDateTime>,
+    pub dawn: Option<NaiveDateTime>, // civil twilight start
+    pub dusk: Option<NaiveDateTime>, // civil twilight end
+    pub golden_morning_end: Option<NaiveDateTime>,
+    pub golden_evening_start: Option<NaiveDateTime>,
+}
+
+impl SunTimes {
+    pub fn day_length(&self) -> Option<Duration> {
+        Some(self.sunset? - self.sunrise?)
+    }
+}
+
+pub struct MoonPhase {
+    pub phase: f64,        // 0 = new, 0.25 = first quarter, 0.5 = full, 0.75 = last quarter
+    pub illumination: f64, // lit fraction, 0-1
+}
+
+impl MoonPhase {
+    /// Which of the 8 named phases (0 = new moon … 7 = waning crescent).
+    pub fn index(&self) -> usize {
+        ((self.phase * 8.0).round() as usize) % 8
+    }
+
+    pub fn glyph(&self) -> &'static str {
+        ["🌑", "🌒", "🌓", "🌔", "🌕", "🌖", "🌗", "🌘"][self.index()]
+    }
+
+    /// Nerd Font glyph for the bar text.
+    pub fn bar_glyph(&self) -> &'static str {
+        [
+            "\u{F0F64}", // 󰽤 nf-md-moon_new
+            "\u{F0F67}", // 󰽧 nf-md-moon_waxing_crescent
+            "\u{F0F61}", // 󰽡 nf-md-moon_first_quarter
+            "\u{F0F68}", // 󰽨 nf-md-moon_waxing_gibbous
+            "\u{F0F62}", // 󰽢 nf-md-moon_full
+            "\u{F0F66}", // 󰽦 nf-md-moon_waning_gibbous
+            "\u{F0F63}", // 󰽣 nf-md-moon_last_quarter
+            "\u{F0F65}", // 󰽥 nf-md-moon_waning_crescent
+        ][self.index()]
+    }
+}
+
+pub struct MoonTimes {
+    pub rise: Option<NaiveDateTime>,
+    pub set: Option<NaiveDateTime>,
+}
+
+// Days since J2000 for a UTC timestamp, and back.
+fn to_days(utc: NaiveDateTime) -> f64 {
+    utc.and_utc().timestamp_millis() as f64 / 86_400_000.0 - 0.5 + J1970 - J2000
+}
+
+fn from_julian(j: f64) -> Option<NaiveDateTime> {
+    if !j.is_finite() {
+        return None;
+    }
+    let millis = ((j + 0.5 - J1970) * 86_400_000.0).round() as i64;
+    DateTime::from_timestamp_millis(millis).map(|dt| dt.naive_utc())
+}
+
+fn right_ascension(l: f64, b: f64) -> f64 {
+    (l.sin() * OBLIQUITY.cos() - b.tan() * OBLIQUITY.sin()).atan2(l.cos())
+}
+
+fn declination(l: f64, b: f64) -> f64 {
+    (b.sin() * OBLIQUITY.cos() + b.cos() * OBLIQUITY.sin() * l.sin()).asin()
+}
+
+fn altitude(hour_angle: f64, phi: f64, dec: f64) -> f64 {
+    (phi.sin() * dec.sin() + phi.cos() * dec.cos() * hour_angle.cos()).asin()
+}
+
+fn sidereal_time(d: f64, lw: f64) -> f64 {
+    RAD * (280.16 + 360.985_623_5 * d) - lw
+}
+
+fn astro_refraction(h: f64) -> f64 {
+    let h = h.max(0.0);
+    0.000_296_7 / (h + 0.003_125_36 / (h + 0.089_011_79)).tan()
+}
+
+fn solar_mean_anomaly(d: f64) -> f64 {
+    RAD * (357.5291 + 0.985_600_28 * d)
+}
+
+fn ecliptic_longitude(m: f64) -> f64 {
+    let center = RAD * (1.9148 * m.sin() + 0.02 * (2.0 * m).sin() + 0.0003 * (3.0 * m).sin());
+    let perihelion = RAD * 102.9372;
+    m + center + perihelion + PI
+}
+
+/// (declination, right ascension) of the sun.
+fn sun_coords(d: f64) -> (f64, f64) {
+    let l = ecliptic_longitude(solar_mean_anomaly(d));
+    (declination(l, 0.0), right_ascension(l, 0.0))
+}
+
+/// (declination, right ascension, distance in km) of the moon.
+fn moon_coords(d: f64) -> (f64, f64, f64) {
+    let l = RAD * (218.316 + 13.176_396 * d); // ecliptic longitude
+    let m = RAD * (134.963 + 13.064_993 * d); // mean anomaly
+    let f = RAD * (93.272 + 13.229_350 * d); // mean distance
+    let lon = l + RAD * 6.289 * m.sin();
+    let lat = RAD * 5.128 * f.sin();
+    let dist = 385_001.0 - 20_905.0 * m.cos();
+    (declination(lon, lat), right_ascension(lon, lat), dist)
+}
+
+fn moon_altitude(utc: NaiveDateTime, lat: f64, lon: f64) -> f64 {
+    let d = to_days(utc);
+    let (dec, ra, _) = moon_coords(d);
+    let h = altitude(sidereal_time(d, RAD * -lon) - ra, RAD * lat, dec);
+    h + astro_refraction(h)
+}
+
+/// Sun events on `date` (location-local), with `offset_seconds` the location's UTC offset.
+pub fn sun_times(date: NaiveDate, lat: f64, lon: f64, offset_seconds: i32) -> SunTimes {
+    let offset = Duration::seconds(offset_seconds as i64);
+    let local_noon = date.and_hms_opt(12, 0, 0).unwrap_or_default();
+    let lw = RAD * -lon;
+    let phi = RAD * lat;
+
+    let d = to_days(local_noon - offset);
+    let n = (d - J0 - lw / (2.0 * PI)).round();
+    let approx_transit = |hour_angle: f64| J0 + (hour_angle + lw) / (2.0 * PI) + n;
+    let ds = approx_transit(0.0);
+    let m = solar_mean_anomaly(ds);
+    let l = ecliptic_longitude(m);
+    let dec = declination(l, 0.0);
+    let transit = |ds: f64| J2000 + ds + 0.0053 * m.sin() - 0.0069 * (2.0 * l).sin();
+    let noon = transit(ds);
+
+    // (rise, set) for the sun's centre crossing `degrees`; NaN from acos means it never does
+    let crossing = |degrees: f64| {
+        let h = degrees * RAD;
+        let w = ((h.sin() - phi.sin() * dec.sin()) / (phi.cos() * dec.cos())).acos();
+        let set = transit(approx_transit(w));
+        let rise = noon - (set - noon);
+        let local = |j: f64| from_julian(j).map(|t| t + offset);
+        (local(rise), local(set))
+    };
+
+    let (sunrise, sunset) = crossing(SUNRISE_ALTITUDE);
+    let (dawn, dusk) = crossing(CIVIL_TWILIGHT_ALTITUDE);
+    let (golden_morning_end, golden_evening_start) = crossing(GOLDEN_HOUR_ALTITUDE);
+    SunTimes {
+        sunrise,
+        sunset,
+        dawn,
+        dusk,
+        golden_morning_end,
+        golden_evening_start,
+    }
+}
+
+/// Moon phase at a UTC instant.
+pub fn moon_phase(utc: NaiveDateTime) -> MoonPhase {
+    let d = to_days(utc);
+    let (s_dec, s_ra) = sun_coords(d);
+    let (m_dec, m_ra, m_dist) = moon_coords(d);
+    let sun_dist = 149_598_000.0; // km
+
+    let elongation = (s_dec.sin() * m_dec.sin() + s_dec.cos() * m_dec.cos() * (s_ra - m_ra).cos()).acos();
+    let inc = (sun_dist * elongation.sin()).atan2(m_dist - sun_dist * elongation.cos());
+    let angle = (s_dec.cos() * (s_ra - m_ra).sin())
+        .atan2(s_dec.sin() * m_dec.cos() - s_dec.cos() * m_dec.sin() * (s_ra - m_ra).cos());
+    let sign = if angle < 0.0 { -1.0 } else { 1.0 };
+
+    MoonPhase {
+        phase: 0.5 + 0.5 * inc * sign / PI,
+        illumination: (1.0 + inc.cos()) / 2.0,
+    }
+}
+
+/// Moonrise/moonset on `date` (location-local). Either can be missing: the moon rises ~50 minutes
+/// later each day, so some dates have no rise or no set.
+pub fn moon_times(date: NaiveDate, lat: f64, lon: f64, offset_seconds: i32) -> MoonTimes {
+    let offset = Duration::seconds(offset_seconds as i64);
+    let start = date.and_hms_opt(0, 0, 0).unwrap_or_default() - offset; // local midnight, in UTC
+    let horizon = 0.133 * RAD;
+    let alt = |hours: f64| moon_altitude(start + Duration::seconds((hours * 3600.0) as i64), lat, lon) - horizon;
+
+    // Fit a parabola through altitudes 2 hours apart and look for horizon crossings
+    let mut rise = None;
+    let mut set = None;
+    let mut h0 = alt(0.0);
+    let mut i = 1.0;
+    while i <= 24.0 {
+        let h1 = alt(i);
+        let h2 = alt(i + 1.0);
+        let a = (h0 + h2) / 2.0 - h1;
+        let b = (h2 - h0) / 2.0;
+        let xe = -b / (2.0 * a);
+        let ye = (a * xe + b) * xe + h1;
+        let disc = b * b - 4.0 * a * h1;
+        if disc >= 0.0 {
+            let dx = disc.sqrt() / (a.abs() * 2.0);
+            let mut x1 = xe - dx;
+            let x2 = xe + dx;
+            let roots = (x1.abs() <= 1.0) as u8 + (x2.abs() <= 1.0) as u8;
+            if x1 < -1.0 {
+                x1 = x2;
+            }
+            match roots {
+                1 if h0 < 0.0 => rise = Some(i + x1),
+                1 => set = Some(i + x1),
+                2 => {
+                    rise = Some(i + if ye < 0.0 { x2 } else { x1 });
+                    set = Some(i + if ye < 0.0 { x1 } else { x2 });
+                }
+                _ => {}
+            }
+        }
+        if rise.is_some() && set.is_some() {
+            break;
+        }
+        h0 = h2;
+        i += 2.0;
+    }
+
+    let local = |hours: f64| start + offset + Duration::seconds((hours * 3600.0) as i64);
+    MoonTimes {
+        rise: rise.map(local),
+        set: set.map(local),
+    }
+}
@@ -1,3 +1,5 @@
+use crate::weather::locale::Locale;
+
 pub const WEATHER_CODES: &[(i32, &str)] = &[
     (0, "☀️"),
     (1, "🌤️"),
@@ -60,6 +62,99 @@ pub const WEATHER_DESCRIPTIONS: &[(i32, &str)] = &[
     (99, "Thunderstorm with heavy hail"),
 ];
 
+pub const WEATHER_DESCRIPTIONS_UK: &[(i32, &str)] = &[
+    (0, "Ясно"),
+    (1, "Переважно ясно"),
+    (2, "Мінлива хмарність"),
+    (3, "Похмуро"),
+    (45, "Туман"),
+    (48, "Туман з інеєм"),
+    (51, "Слабка мряка"),
+    (53, "Помірна мряка"),
+    (55, "Густа мряка"),
+    (56, "Слабка крижана мряка"),
+    (57, "Густа крижана мряка"),
+    (61, "Слабкий дощ"),
+    (63, "Помірний дощ"),
+    (65, "Сильний дощ"),
+    (66, "Слабкий крижаний дощ"),
+    (67, "Сильний крижаний дощ"),
+    (71, "Слабкий снігопад"),
+    (73, "Помірний снігопад"),
+    (75, "Сильний снігопад"),
+    (77, "Снігова крупа"),
+    (80, "Слабка злива"),
+    (81, "Помірна злива"),
+    (82, "Сильна злива"),
+    (85, "Слабкі снігові заряди"),
+    (86, "Сильні снігові заряди"),
+    (95, "Гроза"),
+    (96, "Гроза зі слабким градом"),
+    (99, "Гроза з сильним градом"),
+];
+
+pub const WEATHER_DESCRIPTIONS_PL: &[(i32, &str)] = &[
+    (0, "Bezchmurnie"),
+    (1, "Przeważnie bezchmurnie"),
+    (2, "Częściowe zachmurzenie"),
+    (3, "Pochmurno"),
+    (45, "Mgła"),
+    (48, "Mgła osadzająca szadź"),
+    (51, "Słaba mżawka"),
+    (53, "Umiarkowana mżawka"),
+    (55, "Gęsta mżawka"),
+    (56, "Słaba marznąca mżawka"),
+    (57, "Gęsta marznąca mżawka"),
+    (61, "Słaby deszcz"),
+    (63, "Umiarkowany deszcz"),
+    (65, "Silny deszcz"),
+    (66, "Słaby marznący deszcz"),
+    (67, "Silny marznący deszcz"),
+    (71, "Słabe opady śniegu"),
+    (73, "Umiarkowane opady śniegu"),
+    (75, "Silne opady śniegu"),
+    (77, "Śnieg ziarnisty"),
+    (80, "Słabe przelotne opady deszczu"),
+    (81, "Umiarkowane przelotne opady deszczu"),
+    (82, "Gwałtowne przelotne opady deszczu"),
+    (85, "Słabe przelotne opady śniegu"),
+    (86, "Silne przelotne opady śniegu"),
+    (95, "Burza"),
+    (96, "Burza ze słabym gradem"),
+    (99, "Burza z silnym gradem"),
+];
+
+pub const WEATHER_DESCRIPTIONS_DE: &[(i32, &str)] = &[
+    (0, "Klar"),
+    (1, "Überwiegend klar"),
+    (2, "Teilweise bewölkt"),
+    (3, "Bedeckt"),
+    (45, "Nebel"),
+    (48, "Nebel mit Reifablagerung"),
+    (51, "Leichter Nieselregen"),
+    (53, "Mäßiger Nieselregen"),
+    (55, "Starker Nieselregen"),
+    (56, "Leichter gefrierender Nieselregen"),
+    (57, "Starker gefrierender Nieselregen"),
+    (61, "Leichter Regen"),
+    (63, "Mäßiger Regen"),
+    (65, "Starker Regen"),
+    (66, "Leichter gefrierender Regen"),
+    (67, "Starker gefrierender Regen"),
+    (71, "Leichter Schneefall"),
+    (73, "Mäßiger Schneefall"),
+    (75, "Starker Schneefall"),
+    (77, "Schneegriesel"),
+    (80, "Leichte Regenschauer"),
+    (81, "Mäßige Regenschauer"),
+    (82, "Heftige Regenschauer"),
+    (85, "Leichte Schneeschauer"),
+    (86, "Starke Schneeschauer"),
+    (95, "Gewitter"),
+    (96, "Gewitter mit leichtem Hagel"),
+    (99, "Gewitter mit starkem Hagel"),
+];
+
 pub fn get_icon(code: i32, is_day: bool) -> &'static str {
     if !is_day && (code == 0 || code == 1) {
         return "🌙";
@@ -70,9 +165,15 @@ pub fn get_icon(code: i32, is_day: bool) -> &'static str {
         .unwrap_or("?")
 }
 
-pub fn get_description(code: i32) -> &'static str {
-    WEATHER_DESCRIPTIONS
+pub fn get_description(code: i32, locale: Locale) -> &'static str {
+    let table = match locale {
+        Locale::En => WEATHER_DESCRIPTIONS,
+        Locale::Uk => WEATHER_DESCRIPTIONS_UK,
+        Locale::Pl => WEATHER_DESCRIPTIONS_PL,
+        Locale::De => WEATHER_DESCRIPTIONS_DE,
+    };
+    table
         .binary_search_by(|(k, _)| k.cmp(&code))
-        .map(|i| WEATHER_DESCRIPTIONS[i].1)
+        .map(|i| table[i].1)
         .unwrap_or("Unknown")
 }
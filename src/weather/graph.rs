@@ -0,0 +1,109 @@
+use chrono::NaiveDate;
+
+use crate::weather::locale::Locale;
+use crate::weather::model::Hour;
+use crate::weather::options::GraphOptions;
+
+const BLOCKS: &[char] = &['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// One graph column: several forecast hours averaged together.
+struct Column<'a> {
+    date: &'a str,
+    temp: f64,
+    precip: i32, // max probability in the bucket
+}
+
+/// Block character for `fill` eighths (0 = empty).
+fn block(fill: usize) -> char {
+    match fill {
+        0 => ' ',
+        n => BLOCKS[n.min(8) - 1],
+    }
+}
+
+fn bucket(hours: &[Hour], width: usize) -> Vec<Column<'_>> {
+    let size = hours.len().div_ceil(width).max(1);
+    hours
+        .chunks(size)
+        .map(|chunk| Column {
+            date: chunk[0].time.get(..10).unwrap_or(""),
+            temp: chunk.iter().map(|h| h.temperature).sum::<f64>() / chunk.len() as f64,
+            precip: chunk
+                .iter()
+                .filter_map(|h| h.precipitation_probability)
+                .max()
+                .unwrap_or(0),
+        })
+        .collect()
+}
+
+/// Temperature curve (+ optional precipitation row) over `hours`, `height` rows tall and at most
+/// `width` columns wide. Rows are prefixed with min/max labels (`min–max` on a single row); the
+/// axis marks where each day starts.
+pub fn render(hours: &[Hour], options: &GraphOptions, locale: Locale) -> String {
+    let height = options.height;
+    let columns = bucket(hours, options.width);
+    if columns.len() < 2 {
+        return String::new();
+    }
+    let min = columns.iter().map(|c| c.temp).fold(f64::MAX, f64::min);
+    let max = columns.iter().map(|c| c.temp).fold(f64::MIN, f64::max);
+    let span = (max - min).max(1.0);
+
+    let mut max_label = format!("{}°", max.round() as i32);
+    let min_label = format!("{}°", min.round() as i32);
+    if height == 1 {
+        // A single row is both the top and the bottom one, so it carries the whole range
+        max_label = format!("{}–{max_label}", min.round() as i32);
+    }
+    let label_width = max_label.chars().count().max(min_label.chars().count());
+
+    // Height of each column in eighths of a row; keep at least one eighth so the curve never vanishes.
+    let levels: Vec<usize> = columns
+        .iter()
+        .map(|c| (((c.temp - min) / span) * (height * 8 - 1) as f64).round() as usize + 1)
+        .collect();
+
+    let mut out = String::new();
+    for row in 0..height {
+        let floor = (height - 1 - row) * 8;
+        let label = match row {
+            0 => max_label.as_str(),
+            r if r == height - 1 => min_label.as_str(),
+            _ => "",
+        };
+        let line: String = levels.iter().map(|l| block(l.saturating_sub(floor))).collect();
+        out += &format!("{label:>label_width$} <span foreground=\"#ef9f76\">{line}</span>\n");
+    }
+
+    if options.precip {
+        let line: String = columns
+            .iter()
+            .map(|c| block(((c.precip as f64 / 100.0) * 8.0).round() as usize))
+            .collect();
+        out += &format!("{:>label_width$} <span foreground=\"#8caaee\">{line}</span>\n", "%");
+    }
+
+    // Axis: ┬ where a new day starts, with the weekday written after it
+    let mut axis = String::new();
+    let mut labels: Vec<char> = Vec::new();
+    for (i, c) in columns.iter().enumerate() {
+        let new_day = i == 0 || columns[i - 1].date != c.date;
+        axis.push(if new_day { '┬' } else { '─' });
+        if new_day {
+            // A previous label running past this day's start gets cut off by this one
+            labels.resize(i, ' ');
+            if let Ok(date) = NaiveDate::parse_from_str(c.date, "%Y-%m-%d") {
+                labels.extend(locale.weekday_short(date).chars());
+            }
+        }
+    }
+    let labels: String = labels.into_iter().collect();
+    out += &format!("{:label_width$} {axis}\n", "");
+    out += &format!(
+        "{:label_width$} <span foreground=\"#949cbb\">{}</span>\n",
+        "",
+        labels.trim_end()
+    );
+    out
+}
@@ -0,0 +1,488 @@
+use chrono::{Datelike, NaiveDate, NaiveTime, Timelike};
+
+/// Tooltip language. Picked from WAYBAP_LOCALE, falling back to the system locale (LC_ALL/LC_MESSAGES/LANG).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Locale {
+    En,
+    Uk,
+    Pl,
+    De,
+}
+
+/// Fixed UI strings in the tooltip.
+pub struct Labels {
+    pub today: &'static str,
+    pub tomorrow: &'static str,
+    pub wind: &'static str,
+    pub humidity: &'static str,
+    pub kmh: &'static str,
+    pub precip: &'static str,
+    pub snow: &'static str,
+    pub visibility: &'static str,
+    pub approximate_location: &'static str,
+    pub configured_location: &'static str,
+    pub day_length: &'static str,
+    pub twilight: &'static str,
+    pub golden_hour: &'static str,
+    pub moon_phases: [&'static str; 8], // new moon first, see astro::MoonPhase::index
+    // Templates below are filled by `fill`: {duration}, {time}, {cm}, {hours}, {speed}
+    pub no_rain: &'static str,
+    pub rain_in: &'static str,
+    pub rain_now: &'static str,
+    pub rain_ends: &'static str,
+    pub rain_continues: &'static str,
+    pub now: &'static str, // left end of the nowcast sparkline
+    pub aqi: &'static str,
+    pub eu_aqi_levels: [&'static str; 6], // see utils::european_aqi_level
+    pub us_aqi_levels: [&'static str; 6], // see utils::us_aqi_level
+    pub uv_levels: [&'static str; 5],     // see utils::uv_level
+    pub pollen: &'static str,
+    pub pollen_names: [&'static str; 6], // alder, birch, grass, mugwort, olive, ragweed
+    pub grains: &'static str,
+    pub via: &'static str,
+    pub alert_title: &'static str,
+    pub alert_thunderstorm: &'static str,
+    pub alert_freezing_rain: &'static str,
+    pub alert_snow: &'static str,
+    pub alert_gusts: &'static str,
+    pub alert_frost_below: &'static str,
+    pub alert_frost_above: &'static str,
+}
+
+const EN: Labels = Labels {
+    today: "Today",
+    tomorrow: "Tomorrow",
+    wind: "Wind",
+    humidity: "Humidity",
+    kmh: "km/h",
+    precip: "Precip",
+    snow: "Snow",
+    visibility: "Vis",
+    approximate_location: "approximate location (IP)",
+    configured_location: "configured location",
+    day_length: "Day",
+    twilight: "Twilight",
+    golden_hour: "Golden hour",
+    moon_phases: [
+        "New moon",
+        "Waxing crescent",
+        "First quarter",
+        "Waxing gibbous",
+        "Full moon",
+        "Waning gibbous",
+        "Last quarter",
+        "Waning crescent",
+    ],
+    no_rain: "No rain in the next {duration}",
+    rain_in: "Rain in ~{duration}",
+    rain_now: "Rain now",
+    rain_ends: "ends ~{time}",
+    rain_continues: "continues past {time}",
+    now: "now",
+    aqi: "AQI",
+    eu_aqi_levels: ["Good", "Fair", "Moderate", "Poor", "Very poor", "Extremely poor"],
+    us_aqi_levels: [
+        "Good",
+        "Moderate",
+        "Unhealthy for sensitive groups",
+        "Unhealthy",
+        "Very unhealthy",
+        "Hazardous",
+    ],
+    uv_levels: ["Low", "Moderate", "High", "Very high", "Extreme"],
+    pollen: "Pollen",
+    pollen_names: ["Alder", "Birch", "Grass", "Mugwort", "Olive", "Ragweed"],
+    grains: "grains/m³",
+    via: "via",
+    alert_title: "Weather alert",
+    alert_thunderstorm: "Thunderstorm from {time}",
+    alert_freezing_rain: "Freezing rain from {time}",
+    alert_snow: "Heavy snow: {cm}cm in the next {hours}h",
+    alert_gusts: "Wind gusts up to {speed} km/h at {time}",
+    alert_frost_below: "Temperature drops below 0° at {time}",
+    alert_frost_above: "Temperature rises above 0° at {time}",
+};
+
+const UK: Labels = Labels {
+    today: "Сьогодні",
+    tomorrow: "Завтра",
+    wind: "Вітер",
+    humidity: "Вологість",
+    kmh: "км/год",
+    precip: "Опади",
+    snow: "Сніг",
+    visibility: "Видимість",
+    approximate_location: "приблизне розташування (IP)",
+    configured_location: "задане розташування",
+    day_length: "День",
+    twilight: "Сутінки",
+    golden_hour: "Золота година",
+    moon_phases: [
+        "Молодик",
+        "Молодий місяць",
+        "Перша чверть",
+        "Прибуваючий місяць",
+        "Повня",
+        "Спадний місяць",
+        "Остання чверть",
+        "Старий місяць",
+    ],
+    no_rain: "Без дощу найближчі {duration}",
+    rain_in: "Дощ через ~{duration}",
+    rain_now: "Зараз дощ",
+    rain_ends: "закінчиться ~{time}",
+    rain_continues: "триватиме після {time}",
+    now: "зараз",
+    aqi: "Якість повітря",
+    eu_aqi_levels: [
+        "Добра",
+        "Задовільна",
+        "Помірна",
+        "Погана",
+        "Дуже погана",
+        "Надзвичайно погана",
+    ],
+    us_aqi_levels: [
+        "Добра",
+        "Помірна",
+        "Шкідлива для чутливих груп",
+        "Шкідлива",
+        "Дуже шкідлива",
+        "Небезпечна",
+    ],
+    uv_levels: ["Низький", "Помірний", "Високий", "Дуже високий", "Екстремальний"],
+    pollen: "Пилок",
+    pollen_names: ["Вільха", "Береза", "Трави", "Полин", "Олива", "Амброзія"],
+    grains: "зерен/м³",
+    via: "джерело:",
+    alert_title: "Погодне попередження",
+    alert_thunderstorm: "Гроза з {time}",
+    alert_freezing_rain: "Крижаний дощ з {time}",
+    alert_snow: "Сильний снігопад: {cm} см за {hours} год",
+    alert_gusts: "Пориви вітру до {speed} км/год о {time}",
+    alert_frost_below: "Температура опуститься нижче 0° о {time}",
+    alert_frost_above: "Температура підніметься вище 0° о {time}",
+};
+
+const PL: Labels = Labels {
+    today: "Dzisiaj",
+    tomorrow: "Jutro",
+    wind: "Wiatr",
+    humidity: "Wilgotność",
+    kmh: "km/h",
+    precip: "Opady",
+    snow: "Śnieg",
+    visibility: "Widoczność",
+    approximate_location: "przybliżona lokalizacja (IP)",
+    configured_location: "skonfigurowana lokalizacja",
+    day_length: "Dzień",
+    twilight: "Zmierzch",
+    golden_hour: "Złota godzina",
+    moon_phases: [
+        "Nów",
+        "Przybywający sierp",
+        "Pierwsza kwadra",
+        "Przybywający garb",
+        "Pełnia",
+        "Ubywający garb",
+        "Ostatnia kwadra",
+        "Ubywający sierp",
+    ],
+    no_rain: "Bez deszczu przez następne {duration}",
+    rain_in: "Deszcz za ~{duration}",
+    rain_now: "Teraz pada",
+    rain_ends: "koniec ~{time}",
+    rain_continues: "potrwa po {time}",
+    now: "teraz",
+    aqi: "Jakość powietrza",
+    eu_aqi_levels: [
+        "Dobra",
+        "Zadowalająca",
+        "Umiarkowana",
+        "Zła",
+        "Bardzo zła",
+        "Skrajnie zła",
+    ],
+    us_aqi_levels: [
+        "Dobra",
+        "Umiarkowana",
+        "Niezdrowa dla wrażliwych",
+        "Niezdrowa",
+        "Bardzo niezdrowa",
+        "Niebezpieczna",
+    ],
+    uv_levels: ["Niski", "Umiarkowany", "Wysoki", "Bardzo wysoki", "Ekstremalny"],
+    pollen: "Pyłki",
+    pollen_names: ["Olcha", "Brzoza", "Trawy", "Bylica", "Oliwka", "Ambrozja"],
+    grains: "ziaren/m³",
+    via: "źródło:",
+    alert_title: "Ostrzeżenie pogodowe",
+    alert_thunderstorm: "Burza od {time}",
+    alert_freezing_rain: "Marznący deszcz od {time}",
+    alert_snow: "Intensywne opady śniegu: {cm} cm w ciągu {hours} godz.",
+    alert_gusts: "Porywy wiatru do {speed} km/h o {time}",
+    alert_frost_below: "Temperatura spadnie poniżej 0° o {time}",
+    alert_frost_above: "Temperatura wzrośnie powyżej 0° o {time}",
+};
+
+const DE: Labels = Labels {
+    today: "Heute",
+    tomorrow: "Morgen",
+    wind: "Wind",
+    humidity: "Luftfeuchte",
+    kmh: "km/h",
+    precip: "Niederschlag",
+    snow: "Schnee",
+    visibility: "Sicht",
+    approximate_location: "ungefährer Standort (IP)",
+    configured_location: "konfigurierter Standort",
+    day_length: "Tag",
+    twilight: "Dämmerung",
+    golden_hour: "Goldene Stunde",
+    moon_phases: [
+        "Neumond",
+        "Zunehmende Sichel",
+        "Erstes Viertel",
+        "Zunehmender Mond",
+        "Vollmond",
+        "Abnehmender Mond",
+        "Letztes Viertel",
+        "Abnehmende Sichel",
+    ],
+    no_rain: "Kein Regen in den nächsten {duration}",
+    rain_in: "Regen in ~{duration}",
+    rain_now: "Jetzt Regen",
+    rain_ends: "endet ~{time}",
+    rain_continues: "hält nach {time} an",
+    now: "jetzt",
+    aqi: "Luftqualität",
+    eu_aqi_levels: [
+        "Gut",
+        "Befriedigend",
+        "Mäßig",
+        "Schlecht",
+        "Sehr schlecht",
+        "Extrem schlecht",
+    ],
+    us_aqi_levels: [
+        "Gut",
+        "Mäßig",
+        "Ungesund für Empfindliche",
+        "Ungesund",
+        "Sehr ungesund",
+        "Gefährlich",
+    ],
+    uv_levels: ["Niedrig", "Mäßig", "Hoch", "Sehr hoch", "Extrem"],
+    pollen: "Pollen",
+    pollen_names: ["Erle", "Birke", "Gräser", "Beifuß", "Olive", "Ambrosia"],
+    grains: "Körner/m³",
+    via: "Quelle:",
+    alert_title: "Wetterwarnung",
+    alert_thunderstorm: "Gewitter ab {time}",
+    alert_freezing_rain: "Eisregen ab {time}",
+    alert_snow: "Starker Schneefall: {cm} cm in den nächsten {hours} Std.",
+    alert_gusts: "Windböen bis {speed} km/h um {time}",
+    alert_frost_below: "Temperatur fällt um {time} unter 0°",
+    alert_frost_above: "Temperatur steigt um {time} über 0°",
+};
+
+// Compass points clockwise from north, 45° apart.
+const DIRECTIONS_EN: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+const DIRECTIONS_UK: [&str; 8] = ["Пн", "ПнСх", "Сх", "ПдСх", "Пд", "ПдЗх", "Зх", "ПнЗх"];
+const DIRECTIONS_PL: [&str; 8] = ["Pn", "PnW", "W", "PdW", "Pd", "PdZ", "Z", "PnZ"];
+const DIRECTIONS_DE: [&str; 8] = ["N", "NO", "O", "SO", "S", "SW", "W", "NW"];
+
+// @NOTE: Ukrainian and Polish dates need the genitive month ("19 жовтня", "19 października"),
+//   which is why we don't just use chrono's English `%B`.
+const MONTHS_EN: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const MONTHS_UK: [&str; 12] = [
+    "січня",
+    "лютого",
+    "березня",
+    "квітня",
+    "травня",
+    "червня",
+    "липня",
+    "серпня",
+    "вересня",
+    "жовтня",
+    "листопада",
+    "грудня",
+];
+const MONTHS_PL: [&str; 12] = [
+    "stycznia",
+    "lutego",
+    "marca",
+    "kwietnia",
+    "maja",
+    "czerwca",
+    "lipca",
+    "sierpnia",
+    "września",
+    "października",
+    "listopada",
+    "grudnia",
+];
+const MONTHS_DE: [&str; 12] = [
+    "Januar",
+    "Februar",
+    "März",
+    "April",
+    "Mai",
+    "Juni",
+    "Juli",
+    "August",
+    "September",
+    "Oktober",
+    "November",
+    "Dezember",
+];
+
+const MONTHS_SHORT_EN: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+const MONTHS_SHORT_UK: [&str; 12] = [
+    "січ", "лют", "бер", "кві", "тра", "чер", "лип", "сер", "вер", "жов", "лис", "гру",
+];
+const MONTHS_SHORT_PL: [&str; 12] = [
+    "sty", "lut", "mar", "kwi", "maj", "cze", "lip", "sie", "wrz", "paź", "lis", "gru",
+];
+const MONTHS_SHORT_DE: [&str; 12] = [
+    "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+];
+
+// Monday first, matching chrono's `num_days_from_monday`.
+const WEEKDAYS_EN: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const WEEKDAYS_UK: [&str; 7] = ["Пн", "Вт", "Ср", "Чт", "Пт", "Сб", "Нд"];
+const WEEKDAYS_PL: [&str; 7] = ["Pn", "Wt", "Śr", "Cz", "Pt", "So", "Nd"];
+const WEEKDAYS_DE: [&str; 7] = ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"];
+
+impl Locale {
+    fn from_code(code: &str) -> Option<Locale> {
+        // "uk_UA.UTF-8" → "uk"
+        let lang = code.split(['_', '.', '-', '@']).next().unwrap_or("").to_lowercase();
+        match lang.as_str() {
+            "en" | "c" | "posix" => Some(Locale::En),
+            "uk" => Some(Locale::Uk),
+            "pl" => Some(Locale::Pl),
+            "de" => Some(Locale::De),
+            _ => None,
+        }
+    }
+
+    pub fn from_env() -> Locale {
+        if let Ok(value) = std::env::var("WAYBAP_LOCALE") {
+            return Locale::from_code(&value).unwrap_or_else(|| {
+                eprintln!("WAYBAP_LOCALE: unsupported locale '{value}' (supported: en, uk, pl, de)");
+                Locale::En
+            });
+        }
+        // Unsupported system languages silently fall back to English
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+            .next()
+            .and_then(|v| Locale::from_code(&v))
+            .unwrap_or(Locale::En)
+    }
+
+    pub fn labels(self) -> &'static Labels {
+        match self {
+            Locale::En => &EN,
+            Locale::Uk => &UK,
+            Locale::Pl => &PL,
+            Locale::De => &DE,
+        }
+    }
+
+    /// Compass point for a 0-7 index (N, NE, …).
+    pub fn direction(self, idx: usize) -> &'static str {
+        let table = match self {
+            Locale::En => &DIRECTIONS_EN,
+            Locale::Uk => &DIRECTIONS_UK,
+            Locale::Pl => &DIRECTIONS_PL,
+            Locale::De => &DIRECTIONS_DE,
+        };
+        table[idx % 8]
+    }
+
+    pub fn weekday_short(self, date: NaiveDate) -> &'static str {
+        let table = match self {
+            Locale::En => &WEEKDAYS_EN,
+            Locale::Uk => &WEEKDAYS_UK,
+            Locale::Pl => &WEEKDAYS_PL,
+            Locale::De => &WEEKDAYS_DE,
+        };
+        table[date.weekday().num_days_from_monday() as usize]
+    }
+
+    /// Day header date: "19 October 2026", "19 жовтня 2026", "19. Oktober 2026".
+    pub fn long_date(self, date: NaiveDate) -> String {
+        let table = match self {
+            Locale::En => &MONTHS_EN,
+            Locale::Uk => &MONTHS_UK,
+            Locale::Pl => &MONTHS_PL,
+            Locale::De => &MONTHS_DE,
+        };
+        let month = table[date.month0() as usize];
+        match self {
+            Locale::De => format!("{}. {month} {}", date.day(), date.year()),
+            _ => format!("{} {month} {}", date.day(), date.year()),
+        }
+    }
+
+    /// Compact-mode date: "Mon 19 Oct", "Пн 19 жов", "Mo 19. Okt".
+    pub fn short_date(self, date: NaiveDate) -> String {
+        let table = match self {
+            Locale::En => &MONTHS_SHORT_EN,
+            Locale::Uk => &MONTHS_SHORT_UK,
+            Locale::Pl => &MONTHS_SHORT_PL,
+            Locale::De => &MONTHS_SHORT_DE,
+        };
+        let month = table[date.month0() as usize];
+        let weekday = self.weekday_short(date);
+        match self {
+            Locale::De => format!("{weekday} {}. {month}", date.day()),
+            _ => format!("{weekday} {} {month}", date.day()),
+        }
+    }
+}
+
+/// Fill a `Labels` template: `fill("Rain in ~{duration}", &[("duration", "25m")])`.
+pub fn fill(template: &str, values: &[(&str, &str)]) -> String {
+    values.iter().fold(template.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{name}}}"), value)
+    })
+}
+
+/// "06:12" or "6:12 AM".
+pub fn format_clock(time: NaiveTime, twelve_hour: bool) -> String {
+    if twelve_hour {
+        time.format("%-I:%M %p").to_string()
+    } else {
+        time.format("%H:%M").to_string()
+    }
+}
+
+/// Hour column of hourly rows: "09" or " 9am", fixed width either way.
+pub fn format_hour(time: NaiveTime, twelve_hour: bool) -> String {
+    if twelve_hour {
+        let (pm, hour) = time.hour12();
+        format!("{hour: >2}{}", if pm { "pm" } else { "am" })
+    } else {
+        format!("{:02}", time.hour())
+    }
+}
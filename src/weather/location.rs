@@ -0,0 +1,199 @@
+use chrono::Utc;
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::{self, read_to_string};
+use std::sync::Mutex;
+
+use crate::config::env_parse;
+use crate::scheduler::get_cache_fp;
+
+// Re-run IP geolocation at least this often, even if the public IP looks unchanged.
+const DEFAULT_LOCATION_TTL_SECS: i64 = 6 * 60 * 60;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LocationSource {
+    Configured, // WAYBAP_LAT/WAYBAP_LON
+    Ip,         // ipwho.is geolocation
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Location {
+    pub lat: f64,
+    pub lon: f64,
+    pub city: Option<String>,
+    pub country: Option<String>,
+    pub source: LocationSource,
+    ip: Option<String>, // public IP (from ipify, like the probe) the geolocation was resolved for
+    resolved_at: i64,   // unix seconds
+}
+
+// In-memory copy of the persisted IP location, so we only hit the disk on first use.
+static LOCATION: Mutex<Option<Location>> = Mutex::new(None);
+
+/// `Some(None)` means the user configured coordinates but they are invalid — we fail fast
+/// instead of silently falling back to IP geolocation for a different city.
+fn configured_location() -> Option<Option<Location>> {
+    let lat_env = std::env::var("WAYBAP_LAT").ok();
+    let lon_env = std::env::var("WAYBAP_LON").ok();
+    match (lat_env, lon_env) {
+        (Some(lat_s), Some(lon_s)) => {
+            let lat: f64 = match lat_s.parse() {
+                Ok(v) => v,
+                Err(e) => {
+                    eprintln!("WAYBAP_LAT is not a valid number: {e}");
+                    return Some(None);
+                }
+            };
+            let lon: f64 = match lon_s.parse() {
+                Ok(v) => v,
+                Err(e) => {
+                    eprintln!("WAYBAP_LON is not a valid number: {e}");
+                    return Some(None);
+                }
+            };
+            if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+                eprintln!("WAYBAP_LAT/LON out of range (lat: -90..90, lon: -180..180): {lat}, {lon}");
+                return Some(None);
+            }
+            Some(Some(Location {
+                lat,
+                lon,
+                city: None,
+                country: None,
+                source: LocationSource::Configured,
+                ip: None,
+                resolved_at: Utc::now().timestamp(),
+            }))
+        }
+        (Some(_), None) | (None, Some(_)) => {
+            eprintln!("WAYBAP_LAT and WAYBAP_LON must both be set, ignoring partial config");
+            Some(None)
+        }
+        _ => None,
+    }
+}
+
+fn load_persisted() -> Option<Location> {
+    let text = read_to_string(get_cache_fp("weather-location")).ok()?;
+    match serde_json::from_str::<Location>(&text) {
+        Ok(loc) => Some(loc),
+        Err(e) => {
+            eprintln!("Failed to parse persisted weather location, ignoring: {e}");
+            None
+        }
+    }
+}
+
+fn persist(loc: &Location) {
+    let cache_fp = get_cache_fp("weather-location");
+    let result = serde_json::to_string(loc)
+        .map_err(std::io::Error::other)
+        .and_then(|s| fs::write(&cache_fp, s));
+    if let Err(err) = result {
+        eprintln!("ERROR: failed to write location file '{cache_fp}': {err}");
+    }
+}
+
+/// Cheap public IP probe — used to notice network changes between TTL refreshes
+/// without spending an ipwho.is lookup every cycle.
+fn public_ip(client: &Client) -> Option<String> {
+    let response = match client.get("https://api.ipify.org").send() {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Public IP request failed: {e}");
+            return None;
+        }
+    };
+    if !response.status().is_success() {
+        eprintln!("Public IP request returned HTTP {}", response.status());
+        return None;
+    }
+    let ip = response.text().ok()?.trim().to_string();
+    if ip.is_empty() {
+        None
+    } else {
+        Some(ip)
+    }
+}
+
+fn geolocate(client: &Client) -> Option<Location> {
+    let response = match client.get("https://ipwho.is/").send() {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Geolocation request failed: {e}");
+            return None;
+        }
+    };
+    let text = match response.text() {
+        Ok(t) => t,
+        Err(e) => {
+            eprintln!("Failed to read geolocation response: {e}");
+            return None;
+        }
+    };
+    let geo: Value = match serde_json::from_str(&text) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("Failed to parse geolocation JSON: {e}");
+            return None;
+        }
+    };
+    if geo["success"].as_bool() != Some(true) {
+        let msg = geo["message"].as_str().unwrap_or("unknown error");
+        eprintln!("Geolocation failed: {msg}");
+        return None;
+    }
+    let lat = geo["latitude"].as_f64()?;
+    let lon = geo["longitude"].as_f64()?;
+    Some(Location {
+        lat,
+        lon,
+        city: geo["city"].as_str().map(String::from),
+        country: geo["country"].as_str().map(String::from),
+        source: LocationSource::Ip,
+        // Not ipwho.is's own "ip": it reports IPv6 on dual-stack hosts, so it would never match
+        // the IPv4-only ipify probe. resolve_location() fills this in from the probe.
+        ip: None,
+        resolved_at: Utc::now().timestamp(),
+    })
+}
+
+pub fn resolve_location(client: &Client) -> Option<Location> {
+    if let Some(configured) = configured_location() {
+        return configured;
+    }
+
+    let mut cached = LOCATION.lock().unwrap();
+    if cached.is_none() {
+        *cached = load_persisted();
+    }
+
+    let mut probed_ip = None;
+    if let Some(ref loc) = *cached {
+        let age = Utc::now().timestamp() - loc.resolved_at;
+        if age < env_parse("WAYBAP_LOCATION_TTL", DEFAULT_LOCATION_TTL_SECS) {
+            // @NOTE: A failed probe (offline, ipify down) keeps the known location instead of
+            //   re-resolving — geolocation would most likely fail the same way.
+            match public_ip(client) {
+                Some(ip) if loc.ip.as_deref() != Some(ip.as_str()) => {
+                    eprintln!("INFO: public IP changed, re-resolving weather location");
+                    probed_ip = Some(ip);
+                }
+                _ => return Some(loc.clone()),
+            }
+        }
+    }
+
+    // Only persist on success; on failure keep serving the last known location (if any).
+    match geolocate(client) {
+        Some(mut loc) => {
+            loc.ip = probed_ip.or_else(|| public_ip(client));
+            persist(&loc);
+            *cached = Some(loc.clone());
+            Some(loc)
+        }
+        None => cached.clone(),
+    }
+}
@@ -1,8 +1,17 @@
-//! Weather data from Open-Meteo API (current conditions + 3-day forecast).
+//! Weather data (current conditions + forecast) from Open-Meteo, with MET Norway and wttr.in fallbacks.
+mod alerts;
+mod astro;
 mod constants;
+mod graph;
+mod locale;
+mod location;
+mod model;
+mod nowcast;
+mod options;
 mod parsing;
+mod providers;
 mod query;
 mod utils;
 
-pub use parsing::parse_data;
+pub use parsing::{cache_is_current, parse_data};
 pub use query::query;
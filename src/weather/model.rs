@@ -0,0 +1,173 @@
+//! Provider-independent forecast model. Every `WeatherProvider` converts its response into
+//! a `Forecast`, which is what gets cached and rendered.
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// Weather condition, modelled on the WMO interpretation codes Open-Meteo uses.
+/// Other providers map their own symbols onto the closest variant.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Condition {
+    Clear = 0,
+    MainlyClear = 1,
+    PartlyCloudy = 2,
+    Overcast = 3,
+    Fog = 45,
+    RimeFog = 48,
+    DrizzleLight = 51,
+    DrizzleModerate = 53,
+    DrizzleDense = 55,
+    FreezingDrizzleLight = 56,
+    FreezingDrizzleDense = 57,
+    RainSlight = 61,
+    RainModerate = 63,
+    RainHeavy = 65,
+    FreezingRainLight = 66,
+    FreezingRainHeavy = 67,
+    SnowSlight = 71,
+    SnowModerate = 73,
+    SnowHeavy = 75,
+    SnowGrains = 77,
+    RainShowersSlight = 80,
+    RainShowersModerate = 81,
+    RainShowersViolent = 82,
+    SnowShowersSlight = 85,
+    SnowShowersHeavy = 86,
+    Thunderstorm = 95,
+    ThunderstormSlightHail = 96,
+    ThunderstormHeavyHail = 99,
+    Unknown = -1,
+}
+
+impl Condition {
+    pub fn from_wmo(code: i32) -> Condition {
+        use Condition::*;
+        match code {
+            0 => Clear,
+            1 => MainlyClear,
+            2 => PartlyCloudy,
+            3 => Overcast,
+            45 => Fog,
+            48 => RimeFog,
+            51 => DrizzleLight,
+            53 => DrizzleModerate,
+            55 => DrizzleDense,
+            56 => FreezingDrizzleLight,
+            57 => FreezingDrizzleDense,
+            61 => RainSlight,
+            63 => RainModerate,
+            65 => RainHeavy,
+            66 => FreezingRainLight,
+            67 => FreezingRainHeavy,
+            71 => SnowSlight,
+            73 => SnowModerate,
+            75 => SnowHeavy,
+            77 => SnowGrains,
+            80 => RainShowersSlight,
+            81 => RainShowersModerate,
+            82 => RainShowersViolent,
+            85 => SnowShowersSlight,
+            86 => SnowShowersHeavy,
+            95 => Thunderstorm,
+            96 => ThunderstormSlightHail,
+            99 => ThunderstormHeavyHail,
+            _ => Unknown,
+        }
+    }
+
+    pub fn is_snow(self) -> bool {
+        matches!(self.code(), 71..=77 | 85 | 86)
+    }
+
+    /// WMO code, used to look up icons/descriptions in `constants.rs`.
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Current {
+    pub time: String, // "YYYY-MM-DDTHH:MM", location-local
+    pub temperature: f64,
+    pub apparent_temperature: f64,
+    pub condition: Condition,
+    pub wind_speed: f64, // km/h
+    pub wind_direction: i32,
+    pub humidity: i32,
+    pub is_day: bool,
+    pub uv_index: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Minutely15 {
+    pub time: Vec<String>,
+    pub precipitation: Vec<Option<f64>>, // mm per 15 minutes
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Hour {
+    pub time: String, // "YYYY-MM-DDTHH:MM", location-local
+    pub temperature: f64,
+    pub apparent_temperature: f64,
+    pub condition: Condition,
+    pub precipitation_probability: Option<i32>,
+    pub cloud_cover: Option<i32>,
+    pub snowfall: f64,           // cm
+    pub visibility: Option<f64>, // m
+    pub is_day: bool,
+    pub wind_gusts: Option<f64>, // km/h
+}
+
+impl Hour {
+    /// Start of the hour (or of the 3- or 6-hour step, for wttr and MET's tail).
+    pub fn at(&self) -> Option<NaiveDateTime> {
+        NaiveDateTime::parse_from_str(&self.time, "%Y-%m-%dT%H:%M").ok()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Day {
+    pub date: String, // "YYYY-MM-DD"
+    pub condition: Condition,
+    pub temperature_max: f64,
+    pub temperature_min: f64,
+    pub apparent_temperature_max: f64,
+    pub apparent_temperature_min: f64,
+    pub precipitation_probability_max: Option<i32>,
+    pub sunrise: Option<String>, // "YYYY-MM-DDTHH:MM", location-local
+    pub sunset: Option<String>,
+    pub uv_index_max: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Forecast {
+    pub provider: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub utc_offset_seconds: Option<i32>, // of the location-local timestamps; None = assume the system's
+    pub current: Current,
+    pub minutely_15: Option<Minutely15>,
+    pub hourly: Vec<Hour>,
+    pub daily: Vec<Day>,
+}
+
+impl Forecast {
+    /// Index into `hourly` of the step containing `now`: the last one starting at or before it.
+    /// Series with 3- or 6-hour steps (wttr, MET's tail) rarely have an entry for the exact hour.
+    pub fn current_hour_index(&self, now: NaiveDateTime) -> usize {
+        self.hourly
+            .iter()
+            .rposition(|h| h.at().is_some_and(|at| at <= now))
+            .unwrap_or(0)
+    }
+
+    /// Number of `hourly` steps from `start` that begin within `hours` of `now`, so a window
+    /// covers the same time span whatever the provider's step.
+    pub fn hours_within(&self, start: usize, now: NaiveDateTime, hours: usize) -> usize {
+        let end = now + chrono::Duration::hours(hours as i64);
+        self.hourly[start..]
+            .iter()
+            .take_while(|h| h.at().is_some_and(|at| at <= end))
+            .count()
+    }
+}
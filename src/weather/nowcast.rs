@@ -0,0 +1,103 @@
+use chrono::NaiveDateTime;
+
+use crate::weather::locale::{fill, Labels};
+
+// 15-minute slots shown in the sparkline (2 hours).
+const SPARKLINE_SLOTS: usize = 8;
+// mm per 15 minutes below which we treat the slot as dry (drizzle noise).
+const WET_THRESHOLD_MM: f64 = 0.1;
+// Rain starting within this many minutes is surfaced in the bar text.
+const IMMINENT_MINUTES: i64 = 60;
+
+const SPARK_CHARS: &[char] = &['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+pub struct Nowcast {
+    pub line: String,
+    pub sparkline: String,
+    pub imminent: bool, // raining now, or starting within IMMINENT_MINUTES
+}
+
+fn parse_time(s: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M").ok()
+}
+
+fn format_minutes(mins: i64) -> String {
+    if mins < 60 {
+        format!("{mins}m")
+    } else {
+        format!("{}h {}m", mins / 60, mins % 60)
+    }
+}
+
+/// Build the nowcast from Open-Meteo `minutely_15` data. `now` is the location-local wall clock,
+/// not the cached `current.time`, so countdowns stay right between fetches.
+/// Returns `None` if there is no 15-minute data covering the current time.
+pub fn build(now: NaiveDateTime, times: &[String], precipitation: &[Option<f64>], labels: &Labels) -> Option<Nowcast> {
+    // Slots are labelled by their start time; the one containing "now" starts up to 15m earlier.
+    // A precipitation array shorter than `times` just ends the data early.
+    let slots: Vec<(NaiveDateTime, f64)> = times
+        .iter()
+        .zip(precipitation)
+        .filter_map(|(t, p)| Some((parse_time(t)?, p.unwrap_or(0.0))))
+        .skip_while(|(t, _)| *t + chrono::Duration::minutes(15) <= now)
+        .collect();
+    if slots.is_empty() {
+        return None;
+    }
+    let is_wet = |mm: f64| mm >= WET_THRESHOLD_MM;
+    let last_end = slots[slots.len() - 1].0 + chrono::Duration::minutes(15);
+
+    let (line, imminent) = match slots.iter().position(|(_, mm)| is_wet(*mm)) {
+        None => (
+            fill(
+                labels.no_rain,
+                &[("duration", &format_minutes((last_end - now).num_minutes()))],
+            ),
+            false,
+        ),
+        Some(first_wet) => {
+            let begins = slots[first_wet].0;
+            let ends = slots[first_wet..].iter().find(|(_, mm)| !is_wet(*mm)).map(|(t, _)| *t);
+            let ends_text = match ends {
+                Some(t) => fill(labels.rain_ends, &[("time", &t.format("%H:%M").to_string())]),
+                None => fill(
+                    labels.rain_continues,
+                    &[("time", &last_end.format("%H:%M").to_string())],
+                ),
+            };
+            if begins <= now {
+                (format!("{}, {ends_text}", labels.rain_now), true)
+            } else {
+                let mins = (begins - now).num_minutes();
+                (
+                    format!(
+                        "{}, {ends_text}",
+                        fill(labels.rain_in, &[("duration", &format_minutes(mins))])
+                    ),
+                    mins <= IMMINENT_MINUTES,
+                )
+            }
+        }
+    };
+
+    // @NOTE: Scale against at least 1mm/15min so light drizzle doesn't render as a full bar.
+    let spark_slots = &slots[..SPARKLINE_SLOTS.min(slots.len())];
+    let max = spark_slots.iter().map(|(_, mm)| *mm).fold(1.0, f64::max);
+    let sparkline = spark_slots
+        .iter()
+        .map(|(_, mm)| {
+            if !is_wet(*mm) {
+                ' '
+            } else {
+                let idx = ((mm / max) * (SPARK_CHARS.len() - 1) as f64).round() as usize;
+                SPARK_CHARS[idx.min(SPARK_CHARS.len() - 1)]
+            }
+        })
+        .collect();
+
+    Some(Nowcast {
+        line,
+        sparkline,
+        imminent,
+    })
+}
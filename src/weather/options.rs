@@ -0,0 +1,109 @@
+use crate::config::{env_flag, env_list, env_parse};
+use crate::weather::locale::Locale;
+
+// Optional hourly-row columns accepted by WAYBAP_WEATHER_COLUMNS (all shown by default).
+const ALL_COLUMNS: &[&str] = &["precip", "snow", "visibility", "cloud"];
+// Items WAYBAP_WEATHER_BAR_EXTRAS can append to the bar text, in the order given.
+const BAR_EXTRAS: &[&str] = &["sunrise", "sunset", "next_sun", "daylight", "moon"];
+
+/// Which optional condition columns to append to hourly rows.
+pub struct Columns {
+    pub precip: bool,
+    pub snow: bool,
+    pub visibility: bool,
+    pub cloud: bool,
+}
+
+/// Forecast horizon and tooltip layout, read from WAYBAP_* env vars.
+pub struct WeatherOptions {
+    pub forecast_days: u32,         // 1-16, Open-Meteo's limit
+    pub hourly_step: u32,           // show every Nth hour
+    pub hours_ahead: Option<usize>, // only show hourly rows up to N hours from now
+    pub past_hours: usize,          // keep rows up to N hours in the past
+    pub columns: Columns,
+    pub daily_only: bool, // one line per day, no hourly rows
+    pub graph: Option<GraphOptions>,
+    pub locale: Locale,
+    pub twelve_hour: bool, // sunrise/sunset and hourly rows in 12h format
+    pub astro: bool,       // day length, twilight, golden hour and moon lines under each day header
+    pub bar_extras: Vec<String>,
+}
+
+/// Temperature trend graph drawn above the daily sections.
+pub struct GraphOptions {
+    pub width: usize,  // max columns; hours are averaged to fit
+    pub height: usize, // rows of block characters
+    pub precip: bool,  // add a precipitation probability row
+}
+
+impl WeatherOptions {
+    pub fn from_env() -> WeatherOptions {
+        let forecast_days = env_parse("WAYBAP_FORECAST_DAYS", 3_u32).clamp(1, 16);
+        let hours_ahead = match env_parse("WAYBAP_HOURS_AHEAD", 0_usize) {
+            0 => None,
+            n => Some(n),
+        };
+
+        let columns = match env_list("WAYBAP_WEATHER_COLUMNS") {
+            Some(list) => {
+                for col in &list {
+                    if !ALL_COLUMNS.contains(&col.as_str()) {
+                        eprintln!(
+                            "WAYBAP_WEATHER_COLUMNS: unknown column '{col}' (known: {})",
+                            ALL_COLUMNS.join(", ")
+                        );
+                    }
+                }
+                let has = |name: &str| list.iter().any(|c| c == name);
+                Columns {
+                    precip: has("precip"),
+                    snow: has("snow"),
+                    visibility: has("visibility"),
+                    cloud: has("cloud"),
+                }
+            }
+            None => Columns {
+                precip: true,
+                snow: true,
+                visibility: true,
+                cloud: true,
+            },
+        };
+
+        let bar_extras = env_list("WAYBAP_WEATHER_BAR_EXTRAS").unwrap_or_default();
+        for extra in &bar_extras {
+            if !BAR_EXTRAS.contains(&extra.as_str()) {
+                eprintln!(
+                    "WAYBAP_WEATHER_BAR_EXTRAS: unknown item '{extra}' (known: {})",
+                    BAR_EXTRAS.join(", ")
+                );
+            }
+        }
+
+        WeatherOptions {
+            forecast_days,
+            hourly_step: env_parse("WAYBAP_HOURLY_STEP", 3_u32).clamp(1, 24),
+            hours_ahead,
+            past_hours: env_parse("WAYBAP_PAST_HOURS", 2),
+            columns,
+            // @NOTE: 8 rows × 16 days doesn't fit on any screen, so long horizons default to daily-only.
+            daily_only: env_flag("WAYBAP_WEATHER_DAILY_ONLY", forecast_days > 7),
+            graph: env_flag("WAYBAP_WEATHER_GRAPH", true).then(|| GraphOptions {
+                width: env_parse("WAYBAP_GRAPH_WIDTH", 48_usize).clamp(8, 200),
+                height: env_parse("WAYBAP_GRAPH_HEIGHT", 3_usize).clamp(1, 10),
+                precip: env_flag("WAYBAP_GRAPH_PRECIP", true),
+            }),
+            locale: Locale::from_env(),
+            twelve_hour: match std::env::var("WAYBAP_TIME_FORMAT").as_deref() {
+                Ok("12h") => true,
+                Ok("24h") | Err(_) => false,
+                Ok(other) => {
+                    eprintln!("WAYBAP_TIME_FORMAT: expected '12h' or '24h', got '{other}'");
+                    false
+                }
+            },
+            astro: env_flag("WAYBAP_WEATHER_ASTRO", true),
+            bar_extras,
+        }
+    }
+}
@@ -1,60 +1,46 @@
-use chrono::{Local, NaiveDate};
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
 use serde::Deserialize;
 use serde_json::{json, value::from_value, Value};
 
+use crate::weather::alerts::{self, Alert, HourSample};
+use crate::weather::astro::{self, SunTimes};
 use crate::weather::constants::{get_description, get_icon};
+use crate::weather::graph;
+use crate::weather::locale::{format_clock, format_hour, Labels, Locale};
+use crate::weather::location::LocationSource;
+use crate::weather::model::Forecast;
+use crate::weather::nowcast;
+use crate::weather::options::WeatherOptions;
 use crate::weather::utils::*;
 
 #[derive(Deserialize)]
 struct QueryWrapper {
     location_name: Option<String>,
-    data: OpenMeteoResponse,
+    location_source: Option<LocationSource>, // missing in caches written before it was added
+    data: Forecast,
+    air_quality: Option<AirQualityResponse>,
 }
 
 #[derive(Deserialize)]
-struct OpenMeteoResponse {
-    current: CurrentWeather,
-    hourly: HourlyWeather,
-    daily: DailyWeather,
+struct AirQualityResponse {
+    current: AirQualityCurrent,
 }
 
+// @NOTE: Every field is nullable — US AQI and pollen are only modelled for some regions
+//   (pollen is Europe-only, and only during the season).
 #[derive(Deserialize)]
-struct CurrentWeather {
-    time: String,
-    temperature_2m: f64,
-    apparent_temperature: f64,
-    weather_code: i32,
-    wind_speed_10m: f64,
-    wind_direction_10m: i32,
-    relative_humidity_2m: i32,
-    is_day: i32,
-}
-
-#[derive(Deserialize)]
-struct HourlyWeather {
-    time: Vec<String>,
-    temperature_2m: Vec<f64>,
-    apparent_temperature: Vec<f64>,
-    weather_code: Vec<i32>,
-    precipitation_probability: Vec<i32>,
-    cloud_cover: Vec<i32>,
-    snowfall: Vec<f64>,
-    visibility: Vec<f64>,
-    is_day: Vec<i32>,
-}
-
-#[derive(Deserialize)]
-struct DailyWeather {
-    time: Vec<String>,
-    #[allow(dead_code)]
-    weather_code: Vec<i32>,
-    temperature_2m_max: Vec<f64>,
-    temperature_2m_min: Vec<f64>,
-    apparent_temperature_max: Vec<f64>,
-    apparent_temperature_min: Vec<f64>,
-    precipitation_probability_max: Vec<i32>,
-    sunrise: Vec<String>,
-    sunset: Vec<String>,
+struct AirQualityCurrent {
+    european_aqi: Option<f64>,
+    us_aqi: Option<f64>,
+    pm2_5: Option<f64>,
+    pm10: Option<f64>,
+    ozone: Option<f64>,
+    alder_pollen: Option<f64>,
+    birch_pollen: Option<f64>,
+    grass_pollen: Option<f64>,
+    mugwort_pollen: Option<f64>,
+    olive_pollen: Option<f64>,
+    ragweed_pollen: Option<f64>,
 }
 
 fn bar_icon_color(code: i32, is_day: bool) -> &'static str {
@@ -100,121 +86,452 @@ fn bar_icon(code: i32, is_day: bool) -> &'static str {
     }
 }
 
+/// Run the alert engine over the hours from "now" onwards.
+fn upcoming_alerts(forecast: &Forecast, labels: &Labels) -> Vec<Alert> {
+    let now = local_now(forecast);
+    let samples: Vec<HourSample> = forecast.hourly[forecast.current_hour_index(now)..]
+        .iter()
+        .map(|h| HourSample {
+            time: &h.time,
+            code: h.condition.code(),
+            temp: h.temperature,
+            snowfall: h.snowfall,
+            gusts: h.wind_gusts.unwrap_or(0.0),
+        })
+        .collect();
+    alerts::evaluate(&samples, now, labels)
+}
+
+/// Scheduler-side hook: fire desktop notifications for a freshly fetched forecast.
+pub fn notify_alerts(raw_weather: &Value) {
+    match QueryWrapper::deserialize(raw_weather) {
+        Ok(wrapper) => {
+            let labels = Locale::from_env().labels();
+            alerts::notify(&upcoming_alerts(&wrapper.data, labels), labels)
+        }
+        Err(err) => eprintln!("Failed to evaluate weather alerts: {err}"),
+    }
+}
+
+/// AQI line (+ pollutant and pollen lines) for the current-conditions block.
+fn format_air_quality(aq: &AirQualityCurrent, labels: &Labels) -> String {
+    let mut out = String::new();
+
+    let mut aqi_parts = Vec::new();
+    if let Some(aqi) = aq.european_aqi {
+        let (level, color) = european_aqi_level(aqi);
+        let level = labels.eu_aqi_levels[level];
+        aqi_parts.push(format!(
+            "<span foreground=\"{color}\">EU {} ({level})</span>",
+            aqi.round() as i32
+        ));
+    }
+    if let Some(aqi) = aq.us_aqi {
+        let (level, color) = us_aqi_level(aqi);
+        let level = labels.us_aqi_levels[level];
+        aqi_parts.push(format!(
+            "<span foreground=\"{color}\">US {} ({level})</span>",
+            aqi.round() as i32
+        ));
+    }
+    if !aqi_parts.is_empty() {
+        out += &format!("{}: {}\n", labels.aqi, aqi_parts.join(", "));
+    }
+
+    let pollutants: Vec<String> = [("PM2.5", aq.pm2_5), ("PM10", aq.pm10), ("O₃", aq.ozone)]
+        .iter()
+        .filter_map(|(name, v)| v.map(|v| format!("{name} {}", v.round() as i32)))
+        .collect();
+    if !pollutants.is_empty() {
+        out += &format!("<span foreground=\"#949cbb\">{} µg/m³</span>\n", pollutants.join(", "));
+    }
+
+    let pollen: Vec<String> = [
+        aq.alder_pollen,
+        aq.birch_pollen,
+        aq.grass_pollen,
+        aq.mugwort_pollen,
+        aq.olive_pollen,
+        aq.ragweed_pollen,
+    ]
+    .iter()
+    .zip(labels.pollen_names)
+    .map(|(v, name)| (name, v))
+    .filter_map(|(name, v)| v.filter(|v| *v >= 1.0).map(|v| format!("{name} {}", v.round() as i32)))
+    .collect();
+    if !pollen.is_empty() {
+        out += &format!("{}: {} {}\n", labels.pollen, pollen.join(", "), labels.grains);
+    }
+
+    out
+}
+
+/// UTC offset of the forecast's location-local timestamps on `date`.
+fn utc_offset(forecast: &Forecast, date: NaiveDate) -> i32 {
+    forecast.utc_offset_seconds.unwrap_or_else(|| {
+        date.and_hms_opt(12, 0, 0)
+            .and_then(|noon| Local.from_local_datetime(&noon).earliest())
+            .map(|dt| dt.offset().local_minus_utc())
+            .unwrap_or(0)
+    })
+}
+
+/// The wall clock at the forecast's location, for countdowns that shouldn't age with the cache.
+fn local_now(forecast: &Forecast) -> NaiveDateTime {
+    let utc = Utc::now().naive_utc();
+    utc + Duration::seconds(utc_offset(forecast, utc.date()) as i64)
+}
+
+// @NOTE: Sun times are always computed, even when the provider has sunrise/sunset, so day
+//   length, twilight and golden hour are consistent with each other. Only the 🌅/🌇 header
+//   prefers the provider's values.
+fn sun_times(forecast: &Forecast, date: NaiveDate) -> SunTimes {
+    astro::sun_times(date, forecast.latitude, forecast.longitude, utc_offset(forecast, date))
+}
+
+/// Provider sunrise/sunset timestamp ("YYYY-MM-DDTHH:MM"), if it has one.
+fn provider_time(timestamp: Option<&str>) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(timestamp?, "%Y-%m-%dT%H:%M").ok()
+}
+
+/// Day length, twilight, golden hour and moon lines under a day header.
+fn format_astro(forecast: &Forecast, date: NaiveDate, sun: &SunTimes, options: &WeatherOptions) -> String {
+    let labels = options.locale.labels();
+    let clock = |t: Option<NaiveDateTime>| clock_time(t, options.twelve_hour);
+    let mut lines = Vec::new();
+
+    let mut parts = Vec::new();
+    if let Some(length) = sun.day_length() {
+        let change = date
+            .pred_opt()
+            .and_then(|yesterday| sun_times(forecast, yesterday).day_length())
+            .map(|prev| format!(" ({})", format_day_length_change(length - prev)))
+            .unwrap_or_default();
+        parts.push(format!("{} {}{change}", labels.day_length, format_day_length(length)));
+    }
+    if sun.dawn.is_some() || sun.dusk.is_some() {
+        parts.push(format!("{} {}–{}", labels.twilight, clock(sun.dawn), clock(sun.dusk)));
+    }
+    if !parts.is_empty() {
+        lines.push(parts.join("  "));
+    }
+
+    // @NOTE: Near the poles in winter the sun never gets 6° high, so there is no golden hour
+    //   boundary to show (the whole short day is "golden").
+    if let (Some(rise), Some(morning_end), Some(evening_start), Some(set)) = (
+        sun.sunrise,
+        sun.golden_morning_end,
+        sun.golden_evening_start,
+        sun.sunset,
+    ) {
+        lines.push(format!(
+            "{} {}–{}, {}–{}",
+            labels.golden_hour,
+            clock(Some(rise)),
+            clock(Some(morning_end)),
+            clock(Some(evening_start)),
+            clock(Some(set))
+        ));
+    }
+
+    let offset = utc_offset(forecast, date);
+    let noon_utc = date.and_hms_opt(12, 0, 0).unwrap_or_default() - Duration::seconds(offset as i64);
+    let phase = astro::moon_phase(noon_utc);
+    let moon = astro::moon_times(date, forecast.latitude, forecast.longitude, offset);
+    let mut moon_line = format!(
+        "{} {} {}%",
+        phase.glyph(),
+        labels.moon_phases[phase.index()],
+        (phase.illumination * 100.0).round() as i32
+    );
+    if let Some(rise) = moon.rise {
+        moon_line += &format!("  ↑{}", format_clock(rise.time(), options.twelve_hour));
+    }
+    if let Some(set) = moon.set {
+        moon_line += &format!("  ↓{}", format_clock(set.time(), options.twelve_hour));
+    }
+    lines.push(moon_line);
+
+    lines
+        .iter()
+        .map(|line| format!("<span foreground=\"#949cbb\">{line}</span>\n"))
+        .collect()
+}
+
+/// Optional bar-text items from WAYBAP_WEATHER_BAR_EXTRAS.
+fn format_bar_extras(forecast: &Forecast, now: NaiveDateTime, options: &WeatherOptions) -> String {
+    let today = now.date();
+    let sun = sun_times(forecast, today);
+    let sun_item = |glyph: &str, at: Option<NaiveDateTime>| {
+        at.map(|t| {
+            format!(
+                "<span foreground=\"#e5c890\">{glyph}</span> {}",
+                format_clock(t.time(), options.twelve_hour)
+            )
+        })
+    };
+    let sunrise_glyph = "\u{F059C}"; // 󰖜 nf-md-weather_sunset_up
+    let sunset_glyph = "\u{F059B}"; // 󰖛 nf-md-weather_sunset_down
+
+    options
+        .bar_extras
+        .iter()
+        .filter_map(|extra| match extra.as_str() {
+            "sunrise" => sun_item(sunrise_glyph, sun.sunrise),
+            "sunset" => sun_item(sunset_glyph, sun.sunset),
+            "next_sun" => match (sun.sunrise, sun.sunset) {
+                (Some(rise), _) if now < rise => sun_item(sunrise_glyph, Some(rise)),
+                (_, Some(set)) if now < set => sun_item(sunset_glyph, Some(set)),
+                _ => today
+                    .succ_opt()
+                    .and_then(|tomorrow| sun_item(sunrise_glyph, sun_times(forecast, tomorrow).sunrise)),
+            },
+            "daylight" => sun.day_length().map(|length| {
+                format!(
+                    "<span foreground=\"#e5c890\">\u{F05A8}</span> {}", // 󰖨 nf-md-white_balance_sunny
+                    format_day_length(length)
+                )
+            }),
+            "moon" => {
+                let noon_utc = today.and_hms_opt(12, 0, 0).unwrap_or_default()
+                    - Duration::seconds(utc_offset(forecast, today) as i64);
+                let glyph = astro::moon_phase(noon_utc).bar_glyph();
+                Some(format!("<span foreground=\"#babbf1\">{glyph}</span>"))
+            }
+            _ => None,
+        })
+        .map(|item| format!(" {item}"))
+        .collect()
+}
+
+/// Whether a cached forecast is in the normalized format. Caches from before providers were
+/// pluggable hold the raw Open-Meteo response, which the scheduler refetches instead.
+pub fn cache_is_current(raw: &str) -> bool {
+    serde_json::from_str::<QueryWrapper>(raw).is_ok()
+}
+
 pub fn parse_data(raw_weather: Value) -> Result<String, Box<dyn std::error::Error>> {
     let wrapper = from_value::<QueryWrapper>(raw_weather)?;
     let current = &wrapper.data.current;
     let hourly = &wrapper.data.hourly;
     let daily = &wrapper.data.daily;
 
-    let is_day = current.is_day != 0;
-    let icon = get_icon(current.weather_code, is_day);
+    let is_day = current.is_day;
+    let code = current.condition.code();
+    let icon = get_icon(code, is_day);
     let feels = current.apparent_temperature.round() as i32;
     let feels_colored = color_temp(feels);
 
-    let bar_glyph = bar_icon(current.weather_code, is_day);
-    let bar_glyph_color = bar_icon_color(current.weather_code, is_day);
+    let options = WeatherOptions::from_env();
+    let labels = options.locale.labels();
+    let nowcast = wrapper
+        .data
+        .minutely_15
+        .as_ref()
+        .and_then(|m| nowcast::build(local_now(&wrapper.data), &m.time, &m.precipitation, labels));
+    let now = NaiveDateTime::parse_from_str(&current.time, "%Y-%m-%dT%H:%M")?;
+
+    let bar_glyph = bar_icon(code, is_day);
+    let bar_glyph_color = bar_icon_color(code, is_day);
+    // Small umbrella when rain is falling or about to start
+    let rain_indicator = if nowcast.as_ref().is_some_and(|n| n.imminent) {
+        " <span foreground=\"#8caaee\">\u{F054A}</span>" // 󰕊 nf-md-umbrella
+    } else {
+        ""
+    };
+    let bar_extras = format_bar_extras(&wrapper.data, now, &options);
     let text = format!(
-        "<span size=\"x-small\"><span foreground=\"{bar_glyph_color}\">{bar_glyph}</span> {feels_colored}</span>"
+        "<span size=\"x-small\"><span foreground=\"{bar_glyph_color}\">{bar_glyph}</span> {feels_colored}{rain_indicator}{bar_extras}</span>"
     );
 
     let mut tooltip = String::new();
 
-    // Location header
-    if let Some(ref name) = wrapper.location_name {
-        tooltip += &format!("<span size=\"large\">{}</span>\n\n", crate::pango::escape(name));
+    // Severe-condition alerts go first so they're visible without scrolling
+    let alerts = upcoming_alerts(&wrapper.data, labels);
+    for alert in &alerts {
+        tooltip += &format!(
+            "<span foreground=\"#e78284\"><b>⚠ {}</b></span>\n",
+            crate::pango::escape(&alert.message)
+        );
+    }
+    if !alerts.is_empty() {
+        tooltip += "\n";
+    }
+
+    // Location header, with a muted note on where the coordinates came from
+    let location_name = match (&wrapper.location_name, wrapper.location_source) {
+        (Some(name), _) => Some(name.clone()),
+        (None, Some(LocationSource::Configured)) => {
+            Some(format!("{:.2}, {:.2}", wrapper.data.latitude, wrapper.data.longitude))
+        }
+        _ => None,
+    };
+    if let Some(ref name) = location_name {
+        tooltip += &format!("<span size=\"large\">{}</span>\n", crate::pango::escape(name));
+        match wrapper.location_source {
+            Some(LocationSource::Ip) => {
+                tooltip += &format!("<span foreground=\"#949cbb\">{}</span>\n", labels.approximate_location)
+            }
+            Some(LocationSource::Configured) => {
+                tooltip += &format!("<span foreground=\"#949cbb\">{}</span>\n", labels.configured_location)
+            }
+            None => {}
+        }
+        tooltip += "\n";
     }
 
     // Current conditions
-    let temp = current.temperature_2m.round() as i32;
-    let desc = get_description(current.weather_code);
+    let temp = current.temperature.round() as i32;
+    let desc = get_description(code, options.locale);
     tooltip += &format!("{icon} <b>{desc}</b> {}({feels_colored})\n", color_temp(temp));
     tooltip += &format!(
-        "Wind: {} km/h {}\n",
-        current.wind_speed_10m.round() as i32,
-        wind_direction(current.wind_direction_10m)
+        "{}: {} {} {}\n",
+        labels.wind,
+        current.wind_speed.round() as i32,
+        labels.kmh,
+        wind_direction(current.wind_direction, options.locale)
     );
-    tooltip += &format!("Humidity: {}%\n", current.relative_humidity_2m);
-
-    // Parse location-local date and hour from the API response
-    let (today_str, now_hour) = {
-        let parts: Vec<&str> = current.time.split('T').collect();
-        let date = parts.first().ok_or("missing date in current.time")?;
-        let hour: u32 = parts
-            .get(1)
-            .and_then(|t| t.split(':').next())
-            .and_then(|h| h.parse().ok())
-            .unwrap_or(0);
-        (date.to_string(), hour)
-    };
-    let today = NaiveDate::parse_from_str(&today_str, "%Y-%m-%d")?;
-    let start_idx = daily.time.iter().position(|d| d == &today_str).unwrap_or(0);
+    tooltip += &format!("{}: {}%\n", labels.humidity, current.humidity);
+    if let Some(uv) = current.uv_index {
+        let (level, _) = uv_level(uv);
+        let level = labels.uv_levels[level];
+        tooltip += &format!("{} ({level})\n", color_uv(uv));
+    }
+    if let Some(ref aq) = wrapper.air_quality {
+        tooltip += &format_air_quality(&aq.current, labels);
+    }
+    if let Some(ref n) = nowcast {
+        tooltip += &format!(
+            "☔ {}\n{} ▕<span foreground=\"#8caaee\">{}</span>▏ +2h\n",
+            n.line, labels.now, n.sparkline
+        );
+    }
+
+    // Location-local date from the API response
+    let today_str = current.time.split('T').next().ok_or("missing date in current.time")?;
+    let start_idx = daily.iter().position(|d| d.date == today_str).unwrap_or(0);
+    let earliest_hour = now - Duration::hours(options.past_hours as i64);
+    let latest_hour = options.hours_ahead.map(|ahead| now + Duration::hours(ahead as i64));
 
     // Use the system clock for Today/Tomorrow labels so stale cache data
     // (e.g. network down for days) doesn't misleadingly label old dates.
     let system_today = Local::now().date_naive();
+    let day_label = |date: NaiveDate| {
+        if date == system_today {
+            Some(labels.today)
+        } else if date == system_today.succ_opt().unwrap_or(system_today) {
+            Some(labels.tomorrow)
+        } else {
+            None
+        }
+    };
+
+    if let Some(ref graph_options) = options.graph {
+        let now = local_now(&wrapper.data);
+        let start = wrapper.data.current_hour_index(now);
+        let end = match options.hours_ahead {
+            Some(ahead) => start + wrapper.data.hours_within(start, now, ahead),
+            None => hourly.len(),
+        };
+        let graph = graph::render(&hourly[start..end], graph_options, options.locale);
+        if !graph.is_empty() {
+            tooltip += &format!("\n{graph}");
+        }
+    }
+
+    if options.daily_only {
+        tooltip += "\n";
+    }
+
+    for day in &daily[start_idx..] {
+        let date = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d")?;
+
+        let max_temp = day.temperature_max.round() as i32;
+        let min_temp = day.temperature_min.round() as i32;
+        let precip_max = match day.precipitation_probability_max {
+            Some(p) => format!("{p: >3}%"),
+            None => "  --".to_string(),
+        };
+        let uv_max = match day.uv_index_max {
+            Some(uv) => format!("  {}", color_uv(uv)),
+            None => String::new(),
+        };
 
-    for day_i in start_idx..daily.time.len() {
-        let date = NaiveDate::parse_from_str(&daily.time[day_i], "%Y-%m-%d")?;
+        // Compact mode: one line per day
+        if options.daily_only {
+            let label = match day_label(date) {
+                Some(label) => label.to_string(),
+                None => options.locale.short_date(date),
+            };
+            let code = day.condition.code();
+            tooltip += &format!(
+                "<b>{label: <10}</b> {} {}/{}  🌧️{precip_max}{uv_max}  {}\n",
+                get_icon(code, true),
+                color_temp_padded(max_temp),
+                color_temp_padded(min_temp),
+                get_description(code, options.locale),
+            );
+            continue;
+        }
 
         tooltip += "\n<b>";
-        if date == system_today {
-            tooltip += "Today, ";
-        } else if date == system_today.succ_opt().unwrap_or(system_today) {
-            tooltip += "Tomorrow, ";
+        if let Some(label) = day_label(date) {
+            tooltip += &format!("{label}, ");
         }
-        tooltip += &format!("{}</b>\n", date.format("%-d %B %Y"));
-
-        let max_temp = daily.temperature_2m_max[day_i].round() as i32;
-        let min_temp = daily.temperature_2m_min[day_i].round() as i32;
-        let max_feels = daily.apparent_temperature_max[day_i].round() as i32;
-        let min_feels = daily.apparent_temperature_min[day_i].round() as i32;
-        let precip_max = daily.precipitation_probability_max[day_i];
-        let sunrise = daily.sunrise[day_i].split('T').nth(1).unwrap_or("??:??");
-        let sunset = daily.sunset[day_i].split('T').nth(1).unwrap_or("??:??");
+        tooltip += &format!("{}</b>\n", options.locale.long_date(date));
+
+        let max_feels = day.apparent_temperature_max.round() as i32;
+        let min_feels = day.apparent_temperature_min.round() as i32;
+        // Provider sun times where available, computed otherwise (MET Norway has none);
+        // both missing means polar day/night.
+        let sun_times = sun_times(&wrapper.data, date);
+        let sunrise = provider_time(day.sunrise.as_deref()).or(sun_times.sunrise);
+        let sunset = provider_time(day.sunset.as_deref()).or(sun_times.sunset);
+        let sun = if sunrise.is_some() || sunset.is_some() {
+            format!(
+                "  🌅{} 🌇{}",
+                clock_time(sunrise, options.twelve_hour),
+                clock_time(sunset, options.twelve_hour)
+            )
+        } else {
+            String::new()
+        };
         tooltip += &format!(
-            "🌡️↑ {}({}) 🌡️↓ {}({})  🌧️{precip_max}%  🌅{sunrise} 🌇{sunset}\n",
+            "🌡️↑ {}({}) 🌡️↓ {}({})  🌧️{}{sun}{uv_max}\n",
             color_temp(max_temp),
             color_temp(max_feels),
             color_temp(min_temp),
             color_temp(min_feels),
+            precip_max.trim_start(),
         );
+        if options.astro {
+            tooltip += &format_astro(&wrapper.data, date, &sun_times, &options);
+        }
 
         // Hourly entries for this day
-        let h_start = day_i * 24;
-        let h_end = ((day_i + 1) * 24).min(hourly.time.len());
-
-        for h in h_start..h_end {
-            // Extract hour from time string "YYYY-MM-DDTHH:MM"
-            let hour_str = hourly.time[h].split('T').nth(1).unwrap_or("00:00");
-            let hour_num: u32 = hour_str.split(':').next().unwrap_or("0").parse().unwrap_or(0);
-
-            // Filter to 3-hour intervals
-            if !hour_num.is_multiple_of(3) {
+        for hour in hourly.iter().filter(|h| h.time.starts_with(&day.date)) {
+            let Ok(at) = NaiveDateTime::parse_from_str(&hour.time, "%Y-%m-%dT%H:%M") else {
+                continue;
+            };
+            if !at.hour().is_multiple_of(options.hourly_step) {
                 continue;
             }
 
-            // Skip past hours on today (>2 hours ago)
-            if date == today && now_hour >= 2 && hour_num < now_hour - 2 {
+            // Skip hours too far in the past, or beyond the hours-ahead window
+            if at < earliest_hour || latest_hour.is_some_and(|latest| at > latest) {
                 continue;
             }
 
-            let h_is_day = hourly.is_day[h] != 0;
-            let h_code = hourly.weather_code[h];
-            let h_icon = get_icon(h_code, h_is_day);
-            let h_temp = hourly.temperature_2m[h].round() as i32;
-            let h_feels = hourly.apparent_temperature[h].round() as i32;
-            let h_desc = get_description(h_code);
-            let conditions = format_conditions(
-                h_code,
-                hourly.precipitation_probability[h],
-                hourly.cloud_cover[h],
-                hourly.snowfall[h],
-                hourly.visibility[h],
-            );
+            let h_code = hour.condition.code();
+            let h_icon = get_icon(h_code, hour.is_day);
+            let h_temp = hour.temperature.round() as i32;
+            let h_feels = hour.apparent_temperature.round() as i32;
+            let h_desc = get_description(h_code, options.locale);
+            let conditions = format_conditions(h_code, hour, &options);
 
             tooltip += &format!(
-                "{:02} {} {}({}) {}{}\n",
-                hour_num,
+                "{} {} {}({}) {}{}\n",
+                format_hour(at.time(), options.twelve_hour),
                 h_icon,
                 color_temp_padded(h_temp),
                 color_temp(h_feels),
@@ -224,8 +541,14 @@ pub fn parse_data(raw_weather: Value) -> Result<String, Box<dyn std::error::Erro
         }
     }
 
+    tooltip += &format!(
+        "\n<span foreground=\"#949cbb\">{} {}</span>",
+        labels.via, wrapper.data.provider
+    );
+
     Ok(serde_json::to_string(&json!({
         "text": text,
         "tooltip": format!("<tt>{tooltip}</tt>"),
+        "class": if alerts.is_empty() { "" } else { "alert" },
     }))?)
 }
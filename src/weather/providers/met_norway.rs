@@ -0,0 +1,232 @@
+use chrono::{DateTime, Local, Timelike, Utc};
+use reqwest::blocking::Client;
+use serde_json::Value;
+
+use super::WeatherProvider;
+use crate::weather::location::Location;
+use crate::weather::model::{Condition, Current, Day, Forecast, Hour};
+use crate::weather::options::WeatherOptions;
+
+/// MET Norway locationforecast 2.0 (api.met.no). Free, no key, but requires an identifying User-Agent.
+pub struct MetNorway;
+
+/// Map a MET symbol code (without the `_day`/`_night`/`_polartwilight` suffix) onto a WMO-like condition.
+fn condition_from_symbol(symbol: &str) -> Condition {
+    use Condition::*;
+    match symbol {
+        "clearsky" => Clear,
+        "fair" => MainlyClear,
+        "partlycloudy" => PartlyCloudy,
+        "cloudy" => Overcast,
+        "fog" => Fog,
+        "lightrain" => RainSlight,
+        "rain" => RainModerate,
+        "heavyrain" => RainHeavy,
+        "lightrainshowers" => RainShowersSlight,
+        "rainshowers" => RainShowersModerate,
+        "heavyrainshowers" => RainShowersViolent,
+        // Sleet has no WMO equivalent. Snow showers are the closest wet-snow codes; freezing rain
+        // (66/67) would raise the freezing_rain alert for ordinary sleet.
+        "lightsleet" | "lightsleetshowers" => SnowShowersSlight,
+        "sleet" | "heavysleet" | "sleetshowers" | "heavysleetshowers" => SnowShowersHeavy,
+        "lightsnow" => SnowSlight,
+        "snow" => SnowModerate,
+        "heavysnow" => SnowHeavy,
+        "lightsnowshowers" | "snowshowers" => SnowShowersSlight,
+        "heavysnowshowers" => SnowShowersHeavy,
+        s if s.contains("thunder") => Thunderstorm,
+        _ => Unknown,
+    }
+}
+
+/// Environment Canada wind chill — MET doesn't provide a feels-like temperature.
+fn apparent_temperature(temp: f64, wind_kmh: f64) -> f64 {
+    if temp > 10.0 || wind_kmh < 4.8 {
+        return temp;
+    }
+    let v = wind_kmh.powf(0.16);
+    13.12 + 0.6215 * temp - 11.37 * v + 0.3965 * temp * v
+}
+
+/// Symbol for the shortest forecast period available: hourly steps carry `next_1_hours`,
+/// the 6-hourly tail of the series only `next_6_hours`.
+fn period(data: &Value) -> &Value {
+    if data["next_1_hours"].is_object() {
+        &data["next_1_hours"]
+    } else {
+        &data["next_6_hours"]
+    }
+}
+
+struct Step {
+    local: DateTime<Local>,
+    temperature: f64,
+    wind_kmh: f64,
+    wind_direction: i32,
+    humidity: i32,
+    cloud_cover: Option<i32>,
+    gusts: Option<f64>,
+    uv: Option<f64>,
+    condition: Condition,
+    is_day: bool,
+    precipitation: f64,
+    precipitation_probability: Option<i32>,
+}
+
+fn parse_step(entry: &Value) -> Option<Step> {
+    let time = entry["time"].as_str()?;
+    let utc: DateTime<Utc> = DateTime::parse_from_rfc3339(time).ok()?.into();
+    let details = &entry["data"]["instant"]["details"];
+    let period = period(&entry["data"]);
+    let symbol = period["summary"]["symbol_code"].as_str().unwrap_or("");
+    let (base, variant) = symbol.split_once('_').unwrap_or((symbol, ""));
+    let wind_kmh = details["wind_speed"].as_f64().unwrap_or(0.0) * 3.6;
+    Some(Step {
+        local: utc.with_timezone(&Local),
+        temperature: details["air_temperature"].as_f64()?,
+        wind_kmh,
+        wind_direction: details["wind_from_direction"].as_f64().unwrap_or(0.0).round() as i32,
+        humidity: details["relative_humidity"].as_f64().unwrap_or(0.0).round() as i32,
+        cloud_cover: details["cloud_area_fraction"].as_f64().map(|v| v.round() as i32),
+        gusts: details["wind_speed_of_gust"].as_f64().map(|v| v * 3.6),
+        uv: details["ultraviolet_index_clear_sky"].as_f64(),
+        condition: condition_from_symbol(base),
+        is_day: variant != "night",
+        precipitation: period["details"]["precipitation_amount"].as_f64().unwrap_or(0.0),
+        precipitation_probability: period["details"]["probability_of_precipitation"]
+            .as_f64()
+            .map(|v| v.round() as i32),
+    })
+}
+
+fn normalize(body: &Value, loc: &Location, forecast_days: u32) -> Option<Forecast> {
+    let steps: Vec<Step> = body["properties"]["timeseries"]
+        .as_array()?
+        .iter()
+        .filter_map(parse_step)
+        .collect();
+    let first = steps.first()?;
+
+    let current = Current {
+        time: first.local.format("%Y-%m-%dT%H:%M").to_string(),
+        temperature: first.temperature,
+        apparent_temperature: apparent_temperature(first.temperature, first.wind_kmh),
+        condition: first.condition,
+        wind_speed: first.wind_kmh,
+        wind_direction: first.wind_direction,
+        humidity: first.humidity,
+        is_day: first.is_day,
+        uv_index: first.uv,
+    };
+
+    let hourly: Vec<Hour> = steps
+        .iter()
+        .map(|s| Hour {
+            time: s.local.format("%Y-%m-%dT%H:%M").to_string(),
+            temperature: s.temperature,
+            apparent_temperature: apparent_temperature(s.temperature, s.wind_kmh),
+            condition: s.condition,
+            precipitation_probability: s.precipitation_probability,
+            cloud_cover: s.cloud_cover,
+            // @NOTE: Rough 10:1 snow ratio — 1 mm of water equivalent ≈ 1 cm of snow.
+            snowfall: if s.condition.is_snow() { s.precipitation } else { 0.0 },
+            visibility: None,
+            is_day: s.is_day,
+            wind_gusts: s.gusts,
+        })
+        .collect();
+
+    // Aggregate days from the steps; the representative condition is the one closest to midday.
+    let mut dates: Vec<String> = Vec::new();
+    for s in &steps {
+        let date = s.local.format("%Y-%m-%d").to_string();
+        if !dates.contains(&date) {
+            dates.push(date);
+        }
+    }
+    let daily = dates
+        .into_iter()
+        .take(forecast_days as usize)
+        .filter_map(|date| {
+            let day_steps: Vec<&Step> = steps
+                .iter()
+                .filter(|s| s.local.format("%Y-%m-%d").to_string() == date)
+                .collect();
+            let midday = day_steps.iter().min_by_key(|s| (s.local.hour() as i32 - 12).abs())?;
+            let temps = day_steps.iter().map(|s| s.temperature);
+            let feels = day_steps
+                .iter()
+                .map(|s| apparent_temperature(s.temperature, s.wind_kmh));
+            Some(Day {
+                date,
+                condition: midday.condition,
+                temperature_max: temps.clone().fold(f64::MIN, f64::max),
+                temperature_min: temps.fold(f64::MAX, f64::min),
+                apparent_temperature_max: feels.clone().fold(f64::MIN, f64::max),
+                apparent_temperature_min: feels.fold(f64::MAX, f64::min),
+                precipitation_probability_max: day_steps.iter().filter_map(|s| s.precipitation_probability).max(),
+                sunrise: None,
+                sunset: None,
+                uv_index_max: day_steps.iter().filter_map(|s| s.uv).reduce(f64::max),
+            })
+        })
+        .collect();
+
+    Some(Forecast {
+        provider: "met-norway".to_string(),
+        latitude: loc.lat,
+        longitude: loc.lon,
+        utc_offset_seconds: None, // timestamps were converted to the system timezone above
+        current,
+        minutely_15: None,
+        hourly,
+        daily,
+    })
+}
+
+impl WeatherProvider for MetNorway {
+    fn name(&self) -> &'static str {
+        "met-norway"
+    }
+
+    // @NOTE: MET returns UTC timestamps without the location's timezone, so we convert to the
+    //   system's local timezone — correct as long as the laptop is where the forecast is for.
+    fn fetch(&self, client: &Client, loc: &Location, options: &WeatherOptions) -> Option<Forecast> {
+        // MET asks for at most 4 decimals (more just defeats their cache)
+        let url = format!(
+            "https://api.met.no/weatherapi/locationforecast/2.0/complete?lat={:.4}&lon={:.4}",
+            loc.lat, loc.lon
+        );
+        let response = match client
+            .get(&url)
+            .header("User-Agent", "waybap/0.1.0 github.com/kittyandrew/waybap")
+            .send()
+        {
+            Ok(r) => r,
+            Err(e) => {
+                eprintln!("MET Norway request failed: {e}!");
+                return None;
+            }
+        };
+        if !response.status().is_success() {
+            eprintln!("MET Norway API error: HTTP {}", response.status());
+            return None;
+        }
+        let body: Value = match response.text().map(|t| serde_json::from_str(&t)) {
+            Ok(Ok(v)) => v,
+            Ok(Err(e)) => {
+                eprintln!("Failed to parse MET Norway JSON: {e}");
+                return None;
+            }
+            Err(e) => {
+                eprintln!("Failed to read MET Norway response: {e}");
+                return None;
+            }
+        };
+        let forecast = normalize(&body, loc, options.forecast_days);
+        if forecast.is_none() {
+            eprintln!("Unexpected MET Norway response shape");
+        }
+        forecast
+    }
+}
@@ -0,0 +1,53 @@
+//! Weather backends. Each provider fetches its own API and normalizes into a `Forecast`;
+//! `query()` tries them in the order configured by WAYBAP_WEATHER_PROVIDERS.
+mod met_norway;
+pub mod open_meteo;
+mod wttr;
+
+use reqwest::blocking::Client;
+
+use crate::config::env_list;
+use crate::weather::location::Location;
+use crate::weather::model::Forecast;
+use crate::weather::options::WeatherOptions;
+
+pub trait WeatherProvider {
+    /// Id used in WAYBAP_WEATHER_PROVIDERS and shown in the tooltip footer.
+    fn name(&self) -> &'static str;
+    fn fetch(&self, client: &Client, loc: &Location, options: &WeatherOptions) -> Option<Forecast>;
+}
+
+const DEFAULT_ORDER: &[&str] = &["open-meteo", "met-norway", "wttr"];
+
+fn provider_by_name(name: &str) -> Option<Box<dyn WeatherProvider>> {
+    match name {
+        "open-meteo" => Some(Box::new(open_meteo::OpenMeteo)),
+        "met-norway" => Some(Box::new(met_norway::MetNorway)),
+        "wttr" => Some(Box::new(wttr::Wttr)),
+        _ => None,
+    }
+}
+
+/// Providers in failover order.
+pub fn providers_from_env() -> Vec<Box<dyn WeatherProvider>> {
+    let names =
+        env_list("WAYBAP_WEATHER_PROVIDERS").unwrap_or_else(|| DEFAULT_ORDER.iter().map(|n| n.to_string()).collect());
+    let providers: Vec<_> = names
+        .iter()
+        .filter_map(|name| {
+            let provider = provider_by_name(name);
+            if provider.is_none() {
+                eprintln!(
+                    "WAYBAP_WEATHER_PROVIDERS: unknown provider '{name}' (known: {})",
+                    DEFAULT_ORDER.join(", ")
+                );
+            }
+            provider
+        })
+        .collect();
+    if providers.is_empty() {
+        eprintln!("WAYBAP_WEATHER_PROVIDERS has no valid providers, using default order");
+        return DEFAULT_ORDER.iter().filter_map(|n| provider_by_name(n)).collect();
+    }
+    providers
+}
@@ -0,0 +1,204 @@
+use reqwest::blocking::Client;
+use serde::Deserialize;
+use serde_json::{value::from_value, Value};
+
+use super::WeatherProvider;
+use crate::weather::location::Location;
+use crate::weather::model::{Condition, Current, Day, Forecast, Hour, Minutely15};
+use crate::weather::options::WeatherOptions;
+
+pub struct OpenMeteo;
+
+#[derive(Deserialize)]
+struct OpenMeteoResponse {
+    latitude: f64,
+    longitude: f64,
+    utc_offset_seconds: i32,
+    current: CurrentWeather,
+    minutely_15: Option<Minutely15Weather>,
+    hourly: HourlyWeather,
+    daily: DailyWeather,
+}
+
+#[derive(Deserialize)]
+struct CurrentWeather {
+    time: String,
+    temperature_2m: f64,
+    apparent_temperature: f64,
+    weather_code: i32,
+    wind_speed_10m: f64,
+    wind_direction_10m: i32,
+    relative_humidity_2m: i32,
+    is_day: i32,
+    uv_index: Option<f64>,
+}
+
+// @NOTE: True 15-minute data only exists for Central Europe and North America; elsewhere
+//   Open-Meteo interpolates from hourly, which is still good enough for "rain in ~1h".
+#[derive(Deserialize)]
+struct Minutely15Weather {
+    time: Vec<String>,
+    precipitation: Vec<Option<f64>>,
+}
+
+// @NOTE: Secondary variables go null past their model's horizon (e.g. precipitation
+//   probability on days 10-16), so they are all nullable.
+#[derive(Deserialize)]
+struct HourlyWeather {
+    time: Vec<String>,
+    temperature_2m: Vec<f64>,
+    apparent_temperature: Vec<f64>,
+    weather_code: Vec<i32>,
+    precipitation_probability: Vec<Option<i32>>,
+    cloud_cover: Vec<Option<i32>>,
+    snowfall: Vec<Option<f64>>,
+    visibility: Vec<Option<f64>>,
+    is_day: Vec<i32>,
+    wind_gusts_10m: Vec<Option<f64>>,
+}
+
+#[derive(Deserialize)]
+struct DailyWeather {
+    time: Vec<String>,
+    weather_code: Vec<i32>,
+    temperature_2m_max: Vec<f64>,
+    temperature_2m_min: Vec<f64>,
+    apparent_temperature_max: Vec<f64>,
+    apparent_temperature_min: Vec<f64>,
+    precipitation_probability_max: Vec<Option<i32>>,
+    sunrise: Vec<Option<String>>,
+    sunset: Vec<Option<String>>,
+    uv_index_max: Vec<Option<f64>>,
+}
+
+/// GET an Open-Meteo endpoint and return the parsed body. Open-Meteo reports errors as
+/// `{"error": true, "reason": "..."}`, either with a non-2xx status or inside a 200.
+pub fn fetch_json(client: &Client, url: &str, label: &str) -> Option<Value> {
+    let response = match client.get(url).send() {
+        Ok(r) => r,
+        Err(err) => {
+            eprintln!("{label} request failed: {err}!");
+            return None;
+        }
+    };
+    let status = response.status();
+    let text = match response.text() {
+        Ok(t) => t,
+        Err(e) => {
+            eprintln!("Failed to read {label} response: {e}");
+            return None;
+        }
+    };
+    if !status.is_success() {
+        // Try to extract the "reason" field from error responses (e.g. HTTP 400)
+        let reason = serde_json::from_str::<Value>(&text)
+            .ok()
+            .and_then(|v| v["reason"].as_str().map(String::from))
+            .unwrap_or_else(|| format!("HTTP {status}"));
+        eprintln!("{label} API error: {reason}");
+        return None;
+    }
+    let data: Value = match serde_json::from_str(&text) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("Failed to parse {label} JSON: {e}");
+            return None;
+        }
+    };
+    if data["error"].as_bool() == Some(true) {
+        let reason = data["reason"].as_str().unwrap_or("unknown error");
+        eprintln!("{label} API error: {reason}");
+        return None;
+    }
+    Some(data)
+}
+
+fn normalize(data: OpenMeteoResponse) -> Forecast {
+    let c = data.current;
+    let current = Current {
+        time: c.time,
+        temperature: c.temperature_2m,
+        apparent_temperature: c.apparent_temperature,
+        condition: Condition::from_wmo(c.weather_code),
+        wind_speed: c.wind_speed_10m,
+        wind_direction: c.wind_direction_10m,
+        humidity: c.relative_humidity_2m,
+        is_day: c.is_day != 0,
+        uv_index: c.uv_index,
+    };
+
+    let h = data.hourly;
+    let hourly = (0..h.time.len())
+        .map(|i| Hour {
+            time: h.time[i].clone(),
+            temperature: h.temperature_2m[i],
+            apparent_temperature: h.apparent_temperature[i],
+            condition: Condition::from_wmo(h.weather_code[i]),
+            precipitation_probability: h.precipitation_probability[i],
+            cloud_cover: h.cloud_cover[i],
+            snowfall: h.snowfall[i].unwrap_or(0.0),
+            visibility: h.visibility[i],
+            is_day: h.is_day[i] != 0,
+            wind_gusts: h.wind_gusts_10m[i],
+        })
+        .collect();
+
+    let d = data.daily;
+    let daily = (0..d.time.len())
+        .map(|i| Day {
+            date: d.time[i].clone(),
+            condition: Condition::from_wmo(d.weather_code[i]),
+            temperature_max: d.temperature_2m_max[i],
+            temperature_min: d.temperature_2m_min[i],
+            apparent_temperature_max: d.apparent_temperature_max[i],
+            apparent_temperature_min: d.apparent_temperature_min[i],
+            precipitation_probability_max: d.precipitation_probability_max[i],
+            sunrise: d.sunrise[i].clone(),
+            sunset: d.sunset[i].clone(),
+            uv_index_max: d.uv_index_max[i],
+        })
+        .collect();
+
+    Forecast {
+        provider: "open-meteo".to_string(),
+        latitude: data.latitude,
+        longitude: data.longitude,
+        utc_offset_seconds: Some(data.utc_offset_seconds),
+        current,
+        minutely_15: data.minutely_15.map(|m| Minutely15 {
+            time: m.time,
+            precipitation: m.precipitation,
+        }),
+        hourly,
+        daily,
+    }
+}
+
+impl WeatherProvider for OpenMeteo {
+    fn name(&self) -> &'static str {
+        "open-meteo"
+    }
+
+    fn fetch(&self, client: &Client, loc: &Location, options: &WeatherOptions) -> Option<Forecast> {
+        let url = format!(
+            "https://api.open-meteo.com/v1/forecast\
+             ?latitude={lat}&longitude={lon}\
+             &current=temperature_2m,apparent_temperature,weather_code,wind_speed_10m,wind_direction_10m,relative_humidity_2m,is_day,uv_index\
+             &minutely_15=precipitation&forecast_minutely_15=12\
+             &hourly=temperature_2m,apparent_temperature,weather_code,precipitation_probability,cloud_cover,snowfall,visibility,is_day,wind_gusts_10m\
+             &daily=weather_code,temperature_2m_max,temperature_2m_min,apparent_temperature_max,apparent_temperature_min,precipitation_probability_max,sunrise,sunset,uv_index_max\
+             &timezone=auto&forecast_days={days}",
+            lat = loc.lat,
+            lon = loc.lon,
+            days = options.forecast_days,
+        );
+        let data = fetch_json(client, &url, "Open-Meteo")?;
+        match from_value::<OpenMeteoResponse>(data) {
+            Ok(response) => Some(normalize(response)),
+            Err(e) => {
+                eprintln!("Unexpected Open-Meteo response shape: {e}");
+                None
+            }
+        }
+    }
+}
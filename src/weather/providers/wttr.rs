@@ -0,0 +1,204 @@
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use reqwest::blocking::Client;
+use serde_json::Value;
+
+use super::WeatherProvider;
+use crate::weather::location::Location;
+use crate::weather::model::{Condition, Current, Day, Forecast, Hour};
+use crate::weather::options::WeatherOptions;
+
+/// wttr.in JSON (`?format=j1`). Our original backend — flaky under load, but a useful last resort.
+/// Always 3 days of 3-hourly data, all numbers encoded as strings, times in location-local 12h format.
+pub struct Wttr;
+
+/// Map a WorldWeatherOnline code (what wttr.in reports) onto a WMO-like condition.
+fn condition_from_wwo(code: i32) -> Condition {
+    use Condition::*;
+    match code {
+        113 => Clear,
+        116 => PartlyCloudy,
+        119 | 122 => Overcast,
+        143 | 248 => Fog,
+        260 => RimeFog,
+        176 | 353 => RainShowersSlight,
+        356 => RainShowersModerate,
+        359 => RainShowersViolent,
+        // Sleet (182, 317, 320, 362, 365) is not freezing rain, so it can't raise that alert
+        179 | 182 | 317 | 362 | 365 | 368 => SnowShowersSlight,
+        320 | 371 => SnowShowersHeavy,
+        311 => FreezingRainLight,
+        314 => FreezingRainHeavy,
+        185 | 281 => FreezingDrizzleLight,
+        284 => FreezingDrizzleDense,
+        263 | 266 => DrizzleLight,
+        293 | 296 => RainSlight,
+        299 | 302 => RainModerate,
+        305 | 308 => RainHeavy,
+        227 | 323 | 326 => SnowSlight,
+        329 | 332 => SnowModerate,
+        230 | 335 | 338 => SnowHeavy,
+        350 | 374 | 377 => SnowGrains,
+        200 | 386 | 389 | 392 | 395 => Thunderstorm,
+        _ => Unknown,
+    }
+}
+
+/// wttr.in encodes every number as a string.
+fn num(v: &Value) -> Option<f64> {
+    v.as_str()?.trim().parse().ok()
+}
+
+fn int(v: &Value) -> Option<i32> {
+    num(v).map(|f| f.round() as i32)
+}
+
+fn parse_clock(s: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(s.trim(), "%I:%M %p").ok()
+}
+
+fn normalize(body: &Value, loc: &Location) -> Option<Forecast> {
+    let cc = &body["current_condition"][0];
+    let days = body["weather"].as_array()?;
+
+    let obs = NaiveDateTime::parse_from_str(cc["localObsDateTime"].as_str()?, "%Y-%m-%d %I:%M %p").ok()?;
+    // Sun times per date, to derive is_day (wttr.in has no such flag)
+    let sun: Vec<(NaiveDate, Option<NaiveTime>, Option<NaiveTime>)> = days
+        .iter()
+        .filter_map(|d| {
+            let date = NaiveDate::parse_from_str(d["date"].as_str()?, "%Y-%m-%d").ok()?;
+            let astro = &d["astronomy"][0];
+            Some((
+                date,
+                astro["sunrise"].as_str().and_then(parse_clock),
+                astro["sunset"].as_str().and_then(parse_clock),
+            ))
+        })
+        .collect();
+    let is_day_at = |at: NaiveDateTime| match sun.iter().find(|(d, _, _)| *d == at.date()) {
+        Some((_, Some(rise), Some(set))) => at.time() >= *rise && at.time() < *set,
+        _ => true,
+    };
+
+    let current = Current {
+        time: obs.format("%Y-%m-%dT%H:%M").to_string(),
+        temperature: num(&cc["temp_C"])?,
+        apparent_temperature: num(&cc["FeelsLikeC"])?,
+        condition: condition_from_wwo(int(&cc["weatherCode"])?),
+        wind_speed: num(&cc["windspeedKmph"]).unwrap_or(0.0),
+        wind_direction: int(&cc["winddirDegree"]).unwrap_or(0),
+        humidity: int(&cc["humidity"]).unwrap_or(0),
+        is_day: is_day_at(obs),
+        uv_index: num(&cc["uvIndex"]),
+    };
+
+    let mut hourly = Vec::new();
+    let mut daily = Vec::new();
+    for d in days {
+        let date_str = d["date"].as_str()?;
+        let date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d").ok()?;
+        let hours = d["hourly"].as_array()?;
+        let mut day_hours = Vec::new();
+        for h in hours {
+            // "0", "300", ..., "2100" → hour of day
+            let hhmm = int(&h["time"])?;
+            let at = date.and_hms_opt((hhmm / 100) as u32, 0, 0)?;
+            let condition = condition_from_wwo(int(&h["weatherCode"])?);
+            let precip_mm = num(&h["precipMM"]).unwrap_or(0.0);
+            let chance = int(&h["chanceofrain"])
+                .unwrap_or(0)
+                .max(int(&h["chanceofsnow"]).unwrap_or(0));
+            day_hours.push(Hour {
+                time: at.format("%Y-%m-%dT%H:%M").to_string(),
+                temperature: num(&h["tempC"])?,
+                apparent_temperature: num(&h["FeelsLikeC"])?,
+                condition,
+                precipitation_probability: Some(chance),
+                cloud_cover: int(&h["cloudcover"]),
+                // @NOTE: Rough 10:1 snow ratio — 1 mm of water equivalent ≈ 1 cm of snow.
+                snowfall: if condition.is_snow() { precip_mm } else { 0.0 },
+                visibility: num(&h["visibility"]).map(|km| km * 1000.0),
+                is_day: is_day_at(at),
+                wind_gusts: num(&h["WindGustKmph"]),
+            });
+        }
+
+        let midday = day_hours
+            .iter()
+            .find(|h| h.time.ends_with("T12:00"))
+            .or(day_hours.first())?;
+        let astro = &d["astronomy"][0];
+        let sun_time = |key: &str| {
+            astro[key]
+                .as_str()
+                .and_then(parse_clock)
+                .map(|t| format!("{date_str}T{}", t.format("%H:%M")))
+        };
+        daily.push(Day {
+            date: date_str.to_string(),
+            condition: midday.condition,
+            temperature_max: num(&d["maxtempC"])?,
+            temperature_min: num(&d["mintempC"])?,
+            apparent_temperature_max: day_hours
+                .iter()
+                .map(|h| h.apparent_temperature)
+                .fold(f64::MIN, f64::max),
+            apparent_temperature_min: day_hours
+                .iter()
+                .map(|h| h.apparent_temperature)
+                .fold(f64::MAX, f64::min),
+            precipitation_probability_max: day_hours.iter().filter_map(|h| h.precipitation_probability).max(),
+            sunrise: sun_time("sunrise"),
+            sunset: sun_time("sunset"),
+            uv_index_max: num(&d["uvIndex"]),
+        });
+        hourly.extend(day_hours);
+    }
+
+    Some(Forecast {
+        provider: "wttr".to_string(),
+        latitude: loc.lat,
+        longitude: loc.lon,
+        utc_offset_seconds: None,
+        current,
+        minutely_15: None,
+        hourly,
+        daily,
+    })
+}
+
+impl WeatherProvider for Wttr {
+    fn name(&self) -> &'static str {
+        "wttr"
+    }
+
+    fn fetch(&self, client: &Client, loc: &Location, _options: &WeatherOptions) -> Option<Forecast> {
+        let url = format!("https://wttr.in/{:.4},{:.4}?format=j1", loc.lat, loc.lon);
+        let response = match client.get(&url).send() {
+            Ok(r) => r,
+            Err(e) => {
+                eprintln!("wttr.in request failed: {e}!");
+                return None;
+            }
+        };
+        if !response.status().is_success() {
+            eprintln!("wttr.in API error: HTTP {}", response.status());
+            return None;
+        }
+        let body: Value = match response.text().map(|t| serde_json::from_str(&t)) {
+            Ok(Ok(v)) => v,
+            Ok(Err(e)) => {
+                eprintln!("Failed to parse wttr.in JSON: {e}");
+                return None;
+            }
+            Err(e) => {
+                eprintln!("Failed to read wttr.in response: {e}");
+                return None;
+            }
+        };
+        let forecast = normalize(&body, loc);
+        if forecast.is_none() {
+            eprintln!("Unexpected wttr.in response shape");
+        }
+        forecast
+    }
+}
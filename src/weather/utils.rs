@@ -1,25 +1,54 @@
-const DIRECTIONS: &[&str] = &["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+use chrono::{Duration, NaiveDateTime};
 
-pub fn wind_direction(degrees: i32) -> &'static str {
+use crate::weather::locale::{format_clock, Locale};
+use crate::weather::model::Hour;
+use crate::weather::options::WeatherOptions;
+
+pub fn wind_direction(degrees: i32, locale: Locale) -> &'static str {
     let idx = ((degrees.rem_euclid(360) as f64 / 45.0).round() as usize) % 8;
-    DIRECTIONS[idx]
+    locale.direction(idx)
+}
+
+/// Clock time in the configured 12h/24h format, or "??:??" if missing.
+pub fn clock_time(at: Option<NaiveDateTime>, twelve_hour: bool) -> String {
+    at.map(|t| format_clock(t.time(), twelve_hour))
+        .unwrap_or_else(|| "??:??".to_string())
+}
+
+/// "11h 28m"
+pub fn format_day_length(length: Duration) -> String {
+    format!("{}h {:02}m", length.num_hours(), length.num_minutes() % 60)
+}
+
+/// Change in day length vs. the day before: "+2m", "−3m", or seconds near the solstices.
+pub fn format_day_length_change(change: Duration) -> String {
+    let sign = if change < Duration::zero() { "−" } else { "+" };
+    let secs = change.num_seconds().abs();
+    if secs < 60 {
+        format!("{sign}{secs}s")
+    } else {
+        format!("{sign}{}m", (secs as f64 / 60.0).round() as i64)
+    }
 }
 
-pub fn format_conditions(code: i32, precip: i32, cloud: i32, snow: f64, vis: f64) -> String {
+pub fn format_conditions(code: i32, hour: &Hour, options: &WeatherOptions) -> String {
+    let columns = &options.columns;
+    let labels = options.locale.labels();
     let mut parts = Vec::new();
-    if precip > 0 {
-        parts.push(format!("Precip {precip}%"));
+    if let Some(precip) = hour.precipitation_probability.filter(|p| columns.precip && *p > 0) {
+        parts.push(format!("{} {precip}%", labels.precip));
     }
-    if snow > 0.0 {
-        parts.push(format!("Snow {snow:.1}cm"));
+    if columns.snow && hour.snowfall > 0.0 {
+        parts.push(format!("{} {:.1}cm", labels.snow, hour.snowfall));
     }
-    if vis < 1000.0 {
-        parts.push(format!("Vis {}m", vis.round() as i32));
+    if let Some(vis) = hour.visibility.filter(|v| columns.visibility && *v < 1000.0) {
+        parts.push(format!("{} {}m", labels.visibility, vis.round() as i32));
     }
-    let cloud_suffix = if (code == 0 || code == 1 || code == 2) && cloud > 0 {
-        format!(" (☁️ {cloud}%)")
-    } else {
-        String::new()
+    let cloud_suffix = match hour.cloud_cover {
+        Some(cloud) if columns.cloud && (code == 0 || code == 1 || code == 2) && cloud > 0 => {
+            format!(" (☁️ {cloud}%)")
+        }
+        _ => String::new(),
     };
     if parts.is_empty() {
         cloud_suffix
@@ -51,3 +80,58 @@ pub fn color_temp(temp: i32) -> String {
 pub fn color_temp_padded(temp: i32) -> String {
     color_temp_fmt(format!("{temp: >3}°"), temp)
 }
+
+/// European AQI band (0-20 good … 100+ extremely poor) → (index into `Labels::eu_aqi_levels`, color).
+pub fn european_aqi_level(aqi: f64) -> (usize, &'static str) {
+    if aqi <= 20.0 {
+        (0, "#a6d189")
+    } else if aqi <= 40.0 {
+        (1, "#a6d189")
+    } else if aqi <= 60.0 {
+        (2, "#e5c890")
+    } else if aqi <= 80.0 {
+        (3, "#ef9f76")
+    } else if aqi <= 100.0 {
+        (4, "#e78284")
+    } else {
+        (5, "#ca9ee6")
+    }
+}
+
+/// US EPA AQI band (0-50 good … 300+ hazardous) → (index into `Labels::us_aqi_levels`, color).
+pub fn us_aqi_level(aqi: f64) -> (usize, &'static str) {
+    if aqi <= 50.0 {
+        (0, "#a6d189")
+    } else if aqi <= 100.0 {
+        (1, "#e5c890")
+    } else if aqi <= 150.0 {
+        (2, "#ef9f76")
+    } else if aqi <= 200.0 {
+        (3, "#e78284")
+    } else if aqi <= 300.0 {
+        (4, "#ca9ee6")
+    } else {
+        (5, "#ca9ee6")
+    }
+}
+
+/// WHO UV index band → (index into `Labels::uv_levels`, color).
+pub fn uv_level(uv: f64) -> (usize, &'static str) {
+    if uv < 3.0 {
+        (0, "#a6d189")
+    } else if uv < 6.0 {
+        (1, "#e5c890")
+    } else if uv < 8.0 {
+        (2, "#ef9f76")
+    } else if uv < 11.0 {
+        (3, "#e78284")
+    } else {
+        (4, "#ca9ee6")
+    }
+}
+
+/// Color-code a UV index value: "UV 5".
+pub fn color_uv(uv: f64) -> String {
+    let (_, color) = uv_level(uv);
+    format!("<span foreground=\"{color}\">UV {}</span>", uv.round() as i32)
+}